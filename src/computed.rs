@@ -0,0 +1,263 @@
+//! A module for building reactive values without listing their dependencies by hand.
+//!
+//! `expr::unary` and `expr::binary` need to be told exactly which sources they depend on. A
+//! `computed` expression instead records every observable (or property, or expression) that is
+//! read while its closure runs, and subscribes to exactly those. Dependencies are recorded again on
+//! every run, so a closure which only reads some values in some branches only listens to the ones
+//! it actually read last time.
+//!
+//! `autorun` is the side-effect version of this: a closure which runs right away, and then runs
+//! again whenever anything it read changes.
+
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use expr::{Expression, IntoExpression};
+use obsv::{DisposalHandler, DisposalList, InvalidationHandler, InvalidationList, Observable, ObservablePtr};
+use weak::WeakList;
+
+/// Something a computed closure read: the invalidation and disposal handlers of an observable,
+/// along with the height of the expression it stands for.
+type Dependency = (InvalidationList, DisposalList, usize);
+
+thread_local! {
+    // One frame per closure currently being evaluated, innermost last
+    static FRAMES: RefCell<Vec<Vec<Dependency>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns `true` if a computed closure is currently being evaluated on this thread.
+pub(crate) fn is_tracking() -> bool {
+    FRAMES.with(|frames| !frames.borrow().is_empty())
+}
+
+/// Record that the closure currently being evaluated (if any) read an observable with the given
+/// invalidation and disposal handlers. Plain observables have a height of 0, while the observables
+/// that computed expressions use to notify their listeners are recorded with the height of the
+/// expression.
+pub(crate) fn record_read(on_invalidated: &InvalidationList, on_disposed: &DisposalList, height: usize) {
+    FRAMES.with(|frames| {
+        if let Some(frame) = frames.borrow_mut().last_mut() {
+            if !frame.iter().any(|(dep, _, _)| Rc::ptr_eq(dep, on_invalidated)) {
+                frame.push((on_invalidated.clone(), on_disposed.clone(), height));
+            }
+        }
+    });
+}
+
+/// Pops a tracking frame once evaluation ends, even if the closure panics.
+struct FrameGuard;
+
+impl Drop for FrameGuard {
+    fn drop(&mut self) {
+        FRAMES.with(|frames| frames.borrow_mut().pop());
+    }
+}
+
+/// Run a closure, returning its result along with the invalidation handlers of everything it read.
+fn track<R, F: FnOnce() -> R>(f: F) -> (R, Vec<Dependency>) {
+    FRAMES.with(|frames| frames.borrow_mut().push(Vec::new()));
+    let guard = FrameGuard;
+    let result = f();
+    let deps = FRAMES.with(|frames| frames.borrow_mut().last_mut().map(|frame| frame.split_off(0)));
+    drop(guard);
+    (result, deps.unwrap_or_default())
+}
+
+/// Register a handler with every dependency recorded by `track`, returning the height of the
+/// tallest one. The handler is ranked to fire after all of them.
+fn subscribe(deps: &[Dependency], handler: &InvalidationHandler) -> usize {
+    let height = deps.iter().map(|&(_, _, height)| height).max().unwrap_or(0);
+    handler.raise_rank(height);
+    for (dep, _, _) in deps {
+        dep.borrow_mut().push(handler.callback());
+    }
+    height
+}
+
+/// Register a disposal handler with every dependency recorded by `track`.
+fn subscribe_disposal(deps: &[Dependency], handler: &DisposalHandler) {
+    for (_, on_disposed, _) in deps {
+        on_disposed.borrow_mut().push(handler.callback());
+    }
+}
+
+/// Create an expression from a closure, which depends on whatever the closure reads.
+///
+/// The closure's value is cached, and only computed again the next time it is read after one of
+/// its dependencies changed.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+/// use pebl::expr::IntoExpression;
+///
+/// let mut use_metric = Property::new(true);
+/// let mut meters = Property::new(1000.0);
+/// let (use_metric_expr, meters_expr) = ((&use_metric).into_expr(), (&meters).into_expr());
+/// let label = computed(move || {
+///     if use_metric_expr.get() {
+///         format!("{} km", meters_expr.get() / 1000.0)
+///     } else {
+///         format!("{} mi", meters_expr.get() / 1609.0)
+///     }
+/// });
+/// assert_eq!("1 km", label.get());
+///
+/// meters.set(2000.0);
+/// assert_eq!("2 km", label.get());
+///
+/// use_metric.set(false);
+/// meters.set(1609.0);
+/// assert_eq!("1 mi", label.get());
+/// ```
+pub fn computed<T: 'static + Clone, F: 'static + Fn() -> T>(f: F) -> Rc<Expression<T>> {
+    Rc::new(ComputedExpression {
+        f: Box::new(f),
+        value: RefCell::new(None),
+        height: Cell::new(1),
+        handler: RefCell::new(None),
+        notifier: Observable::new(()),
+        on_disposed: Rc::new(RefCell::new(WeakList::new())),
+        disposal_handler: RefCell::new(None),
+    })
+}
+
+/// An expression which evaluates a closure, tracking whatever it reads.
+struct ComputedExpression<T> {
+    f: Box<Fn() -> T>,
+    value: RefCell<Option<T>>,
+    // One more than the tallest dependency read on the last run
+    height: Cell<usize>,
+    // Subscribed to whatever the closure read last time it ran. Replaced on every run, which
+    // unsubscribes from the old dependencies. The value is stale once this is dirty.
+    handler: RefCell<Option<InvalidationHandler>>,
+    // Fired when any dependency changes, to let listeners of this expression know
+    notifier: Observable<()>,
+    // Disposal handlers registered with us, which need to hear about any dependency read on the
+    // last run being disposed. Like `handler`, the forwarding handler is replaced on every run.
+    on_disposed: DisposalList,
+    disposal_handler: RefCell<Option<DisposalHandler>>,
+}
+
+impl<T: 'static + Clone> ComputedExpression<T> {
+    fn evaluate(&self) -> T {
+        let notifier = ObservablePtr::new(&self.notifier);
+        let handler = InvalidationHandler::new(move || notifier.deref().invalidate());
+
+        let on_disposed = self.on_disposed.clone();
+        let disposal_handler = DisposalHandler::new(move || {
+            let callbacks = on_disposed.borrow().upgrade();
+            for callback in callbacks {
+                callback();
+            }
+        });
+
+        let (value, deps) = track(|| (self.f)());
+        self.height.set(subscribe(&deps, &handler) + 1);
+        subscribe_disposal(&deps, &disposal_handler);
+        *self.handler.borrow_mut() = Some(handler);
+        *self.disposal_handler.borrow_mut() = Some(disposal_handler);
+        value
+    }
+
+    /// Run the closure if it has never run, since what it depends on isn't known until then.
+    fn evaluate_once(&self) {
+        if self.value.borrow().is_none() {
+            let value = self.evaluate();
+            *self.value.borrow_mut() = Some(value);
+        }
+    }
+}
+
+impl<T: 'static + Clone> IntoExpression<T> for ComputedExpression<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        Rc::new(self)
+    }
+}
+
+impl<T: 'static + Clone> Expression<T> for ComputedExpression<T> {
+    fn try_get(&self) -> Option<T> {
+        let stale = self.handler.borrow().as_ref().is_none_or(|handler| handler.is_dirty());
+        if stale || self.value.borrow().is_none() {
+            let value = self.evaluate();
+            *self.value.borrow_mut() = Some(value);
+        }
+
+        // Let any computed expression reading this one know it depends on us
+        self.notifier.record_read(&self.on_disposed, self.height.get());
+        self.value.borrow().clone()
+    }
+
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
+        self.notifier.add_invalidation_handler(handler);
+    }
+
+    fn height(&self) -> usize {
+        self.evaluate_once();
+        self.height.get()
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        self.on_disposed.borrow_mut().push(handler.callback());
+        self.evaluate_once();
+    }
+}
+
+/// A handle to a closure registered with `autorun`. The closure stops running once this is
+/// dropped.
+#[must_use]
+pub struct Autorun {
+    #[allow(dead_code)] // Needed to keep weak ref alive
+    state: Rc<AutorunState>,
+}
+
+struct AutorunState {
+    f: Box<Fn()>,
+    handler: RefCell<Option<InvalidationHandler>>,
+}
+
+/// Run a closure right away, and then again whenever anything it read changes. The closure keeps
+/// running until the returned handle is dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use pebl::prelude::*;
+/// use pebl::expr::IntoExpression;
+///
+/// let mut name = Property::new(String::from("John"));
+/// let greetings = Rc::new(RefCell::new(Vec::new()));
+/// let run;
+/// {
+///     let name = (&name).into_expr();
+///     let greetings = greetings.clone();
+///     run = autorun(move || greetings.borrow_mut().push(format!("Hello, {}", name.get())));
+/// }
+///
+/// name.set(String::from("Jane"));
+/// drop(run);
+/// name.set(String::from("Joe"));
+/// assert_eq!(vec!["Hello, John", "Hello, Jane"], *greetings.borrow());
+/// ```
+pub fn autorun<F: 'static + Fn()>(f: F) -> Autorun {
+    let state = Rc::new(AutorunState { f: Box::new(f), handler: RefCell::new(None) });
+    run(&state);
+    Autorun { state: state }
+}
+
+fn run(state: &Rc<AutorunState>) {
+    let weak_state: Weak<AutorunState> = Rc::downgrade(state);
+    let handler = InvalidationHandler::new(move || {
+        if let Some(state) = weak_state.upgrade() {
+            run(&state);
+        }
+    });
+
+    let ((), deps) = track(|| (state.f)());
+    subscribe(&deps, &handler);
+    *state.handler.borrow_mut() = Some(handler);
+}
@@ -0,0 +1,105 @@
+//! A module of policies which decide whether a new value is different from an old one.
+//!
+//! Whenever an `Observable<T>` (or `Property<T>`) is set, it only notifies its listeners if the new
+//! value is not equal to the current one. By default, that comparison uses `PartialEq`, but this is
+//! not always what you want: `f32::NAN` is never equal to itself, comparing large collections on
+//! every write can be expensive, and some types don't implement `PartialEq` at all. In those cases,
+//! create the observable `with_policy` and pick one of the policies from this module, or pass in
+//! any `Fn(&T, &T) -> bool` closure.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A strategy for comparing an observable's current value with a new one.
+pub trait EqualityPolicy<T> {
+    /// Return `true` if `old` and `new` should be considered equal, in which case setting `new`
+    /// will not notify any listeners.
+    fn equals(&self, old: &T, new: &T) -> bool;
+}
+
+impl<T, F: Fn(&T, &T) -> bool> EqualityPolicy<T> for F {
+    fn equals(&self, old: &T, new: &T) -> bool {
+        self(old, new)
+    }
+}
+
+/// The default policy, which compares values using `PartialEq`.
+pub struct PartialEqPolicy;
+
+impl<T: PartialEq> EqualityPolicy<T> for PartialEqPolicy {
+    fn equals(&self, old: &T, new: &T) -> bool {
+        old == new
+    }
+}
+
+/// A policy which never considers two values equal, so every `set` notifies listeners. This is
+/// useful for large values where a full comparison costs more than a spurious notification.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use pebl::equality::AlwaysNotify;
+/// use pebl::obsv::{InvalidationHandler, Observable};
+///
+/// let mut val = Observable::with_policy(vec![0; 1000], AlwaysNotify);
+/// let count = Rc::new(Cell::new(0));
+/// let count_clone = count.clone();
+/// let handler = InvalidationHandler::new(move || count_clone.set(count_clone.get() + 1));
+/// val.add_invalidation_handler(&handler);
+///
+/// val.set(vec![0; 1000]);
+/// assert_eq!(1, count.get());
+/// ```
+pub struct AlwaysNotify;
+
+impl<T> EqualityPolicy<T> for AlwaysNotify {
+    fn equals(&self, _old: &T, _new: &T) -> bool {
+        false
+    }
+}
+
+/// A policy for reference-counted values, which considers them equal only if they point at the
+/// same allocation. This is much cheaper than comparing the values being pointed at, and works even
+/// if they don't implement `PartialEq`.
+pub struct PtrEq;
+
+impl<T: ? Sized> EqualityPolicy<Rc<T>> for PtrEq {
+    fn equals(&self, old: &Rc<T>, new: &Rc<T>) -> bool {
+        Rc::ptr_eq(old, new)
+    }
+}
+
+impl<T: ? Sized> EqualityPolicy<Arc<T>> for PtrEq {
+    fn equals(&self, old: &Arc<T>, new: &Arc<T>) -> bool {
+        Arc::ptr_eq(old, new)
+    }
+}
+
+/// A policy for floating point values, which considers them equal if they are within some epsilon
+/// of each other. Unlike `PartialEq`, this also considers `NAN` equal to itself, so setting `NAN`
+/// twice in a row only notifies listeners once.
+///
+/// # Example
+///
+/// ```
+/// use pebl::equality::{Approx, EqualityPolicy};
+///
+/// assert!(Approx(0.01).equals(&1.0, &1.005));
+/// assert!(!Approx(0.01).equals(&1.0, &1.02));
+/// assert!(Approx(0.01).equals(&::std::f64::NAN, &::std::f64::NAN));
+/// ```
+pub struct Approx(pub f64);
+
+impl EqualityPolicy<f64> for Approx {
+    fn equals(&self, old: &f64, new: &f64) -> bool {
+        (old.is_nan() && new.is_nan()) || (old - new).abs() <= self.0
+    }
+}
+
+impl EqualityPolicy<f32> for Approx {
+    fn equals(&self, old: &f32, new: &f32) -> bool {
+        (old.is_nan() && new.is_nan()) || f64::from((old - new).abs()) <= self.0
+    }
+}
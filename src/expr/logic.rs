@@ -6,6 +6,16 @@ pub fn and<E1: IntoExpression<bool>, E2: IntoExpression<bool>>(lhs: E1, rhs: E2)
     ::expr::binary(lhs, rhs, |&val1, &val2| val1 && val2)
 }
 
+/// `true` if `lhs` is `false`, or if both are `true`. In other words, `lhs` being `true` implies
+/// `rhs` is `true` as well.
+pub fn implies<E1: IntoExpression<bool>, E2: IntoExpression<bool>>(lhs: E1, rhs: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(lhs, rhs, |&val1, &val2| !val1 || val2)
+}
+
+pub fn nand<E1: IntoExpression<bool>, E2: IntoExpression<bool>>(lhs: E1, rhs: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(lhs, rhs, |&val1, &val2| !(val1 && val2))
+}
+
 pub fn not<E: IntoExpression<bool>>(value: E) -> Rc<Expression<bool>> {
     ::expr::unary(value, |val| !val)
 }
@@ -13,3 +23,81 @@ pub fn not<E: IntoExpression<bool>>(value: E) -> Rc<Expression<bool>> {
 pub fn or<E1: IntoExpression<bool>, E2: IntoExpression<bool>>(lhs: E1, rhs: E2) -> Rc<Expression<bool>> {
     ::expr::binary(lhs, rhs, |&val1, &val2| val1 || val2)
 }
+
+pub fn xor<E1: IntoExpression<bool>, E2: IntoExpression<bool>>(lhs: E1, rhs: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(lhs, rhs, |&val1, &val2| val1 != val2)
+}
+
+/// Create an expression which is `true` if every one of some boolean expressions is `true`, or if
+/// there are none.
+///
+/// Unlike chaining `and` calls, this creates a single expression no matter how many sources there
+/// are. Sources are checked in order, and checking stops at the first `false` one.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut name_valid = Property::new(true);
+/// let email_valid = Property::new(true);
+/// let mut agreed = Property::new(false);
+/// let can_submit = expr::logic::all(vec![&name_valid, &email_valid, &agreed]);
+/// assert_eq!(false, can_submit.get());
+///
+/// agreed.set(true);
+/// assert_eq!(true, can_submit.get());
+///
+/// name_valid.set(false);
+/// assert_eq!(false, can_submit.get());
+/// ```
+pub fn all<E: IntoExpression<bool>, I: IntoIterator<Item=E>>(values: I) -> Rc<Expression<bool>> {
+    flat(values, |srcs| {
+        for src in srcs {
+            if !src.try_get()? { return Some(false) }
+        }
+        Some(true)
+    })
+}
+
+/// Create an expression which is `true` if at least one of some boolean expressions is `true`.
+/// Checking stops at the first `true` one. See `all`.
+pub fn any<E: IntoExpression<bool>, I: IntoIterator<Item=E>>(values: I) -> Rc<Expression<bool>> {
+    flat(values, |srcs| {
+        for src in srcs {
+            if src.try_get()? { return Some(true) }
+        }
+        Some(false)
+    })
+}
+
+/// Create an expression which is `true` if none of some boolean expressions are `true`. Checking
+/// stops at the first `true` one. See `all`.
+pub fn none<E: IntoExpression<bool>, I: IntoIterator<Item=E>>(values: I) -> Rc<Expression<bool>> {
+    flat(values, |srcs| {
+        for src in srcs {
+            if src.try_get()? { return Some(false) }
+        }
+        Some(true)
+    })
+}
+
+/// Create an expression which counts how many of some boolean expressions are `true`. See `all`.
+pub fn count_true<E: IntoExpression<bool>, I: IntoIterator<Item=E>>(values: I) -> Rc<Expression<usize>> {
+    flat(values, |srcs| {
+        let mut count = 0;
+        for src in srcs {
+            if src.try_get()? { count += 1 }
+        }
+        Some(count)
+    })
+}
+
+/// Combine any number of boolean expressions into a single expression. If `f` returns early,
+/// the remaining sources are never evaluated.
+fn flat<O, E, I, F>(values: I, f: F) -> Rc<Expression<O>>
+    where O: 'static + Clone, E: IntoExpression<bool>, I: IntoIterator<Item=E>, F: 'static + Fn(&[Rc<Expression<bool>>]) -> Option<O> {
+    let srcs: Vec<Rc<Expression<bool>>> = values.into_iter().map(|value| value.into_expr()).collect();
+    let sources: Vec<Box<Source>> = srcs.iter().map(|src| Box::new(src.clone()) as Box<Source>).collect();
+    ::expr::nary(sources, move || f(&srcs))
+}
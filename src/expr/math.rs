@@ -1,25 +1,224 @@
-use std::ops::{Add, Mul, Neg};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 use std::cmp::PartialOrd;
 use std::rc::Rc;
 
 use super::*;
 
+/// Division and remainder which return `None` instead of panicking, for types where that is
+/// possible. Floating point types return `None` when dividing by zero, rather than infinity or NaN.
+pub trait CheckedDiv: Sized {
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+}
+
+/// Raising a number to a whole power, like the integer `pow` methods.
+pub trait Pow {
+    fn pow(self, exp: u32) -> Self;
+}
+
+/// Operations which only make sense for floating point types.
+pub trait Float: Copy + PartialOrd + Add<Output=Self> + Sub<Output=Self> + Mul<Output=Self> {
+    fn powf(self, exp: Self) -> Self;
+    fn sqrt(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+}
+
+macro_rules! int_ops {
+    ($($t:ty),+) => {
+        $(
+            impl CheckedDiv for $t {
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_div(self, rhs)
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_rem(self, rhs)
+                }
+            }
+
+            impl Pow for $t {
+                fn pow(self, exp: u32) -> Self {
+                    <$t>::pow(self, exp)
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! float_ops {
+    ($($t:ty),+) => {
+        $(
+            impl CheckedDiv for $t {
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    if rhs == 0.0 { None } else { Some(self / rhs) }
+                }
+
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    if rhs == 0.0 { None } else { Some(self % rhs) }
+                }
+            }
+
+            impl Pow for $t {
+                fn pow(self, exp: u32) -> Self {
+                    if exp <= i32::MAX as u32 { self.powi(exp as i32) } else { self.powf(exp as $t) }
+                }
+            }
+
+            impl Float for $t {
+                fn powf(self, exp: Self) -> Self { <$t>::powf(self, exp) }
+                fn sqrt(self) -> Self { <$t>::sqrt(self) }
+                fn floor(self) -> Self { <$t>::floor(self) }
+                fn ceil(self) -> Self { <$t>::ceil(self) }
+                fn round(self) -> Self { <$t>::round(self) }
+            }
+        )+
+    }
+}
+
+int_ops!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_ops!(f32, f64);
+
 pub fn abs<T, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>>
-    where T: 'static + PartialEq + Copy + PartialOrd + Default + Neg<Output=T> {
-    ::expr::unary(value, |&val| if val >= Default::default() { val } else { -val })
+    where T: 'static + Clone + PartialOrd + Default + Neg<Output=T> {
+    ::expr::unary(value, |val| if *val >= Default::default() { val.clone() } else { -val.clone() })
 }
 
 pub fn neg<T, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>>
-    where T: 'static + PartialEq + Copy + Neg<Output=T> {
-    ::expr::unary(value, |&val| -val)
+    where T: 'static + Clone + Neg<Output=T> {
+    ::expr::unary(value, |val| -val.clone())
 }
 
 pub fn plus<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
-    where T: 'static + PartialEq + Copy + Add<Output=T> {
-    ::expr::binary(lhs, rhs, |&val1, &val2| val1 + val2)
+    where T: 'static + Clone + Add<Output=T> {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone() + val2.clone())
+}
+
+pub fn minus<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + Sub<Output=T> {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone() - val2.clone())
 }
 
 pub fn times<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
-    where T: 'static + PartialEq + Copy + Mul<Output=T> {
-    ::expr::binary(lhs, rhs, |&val1, &val2| val1 * val2)
+    where T: 'static + Clone + Mul<Output=T> {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone() * val2.clone())
+}
+
+/// Divide one expression by another. Like the `/` operator, this panics if an integer expression is
+/// divided by zero; use `checked_div` if that can happen.
+pub fn div<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + Div<Output=T> {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone() / val2.clone())
+}
+
+/// The remainder of dividing one expression by another. Like the `%` operator, this panics if an
+/// integer expression is divided by zero; use `checked_rem` if that can happen.
+pub fn rem<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + Rem<Output=T> {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone() % val2.clone())
+}
+
+/// Divide one expression by another, with a value of `None` instead of a panic when dividing by
+/// zero.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let total = Property::new(10);
+/// let mut count = Property::new(0);
+/// let average = expr::math::checked_div(&total, &count);
+/// assert_eq!(None, average.get());
+///
+/// count.set(4);
+/// assert_eq!(Some(2), average.get());
+/// ```
+pub fn checked_div<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<Option<T>>>
+    where T: 'static + Clone + CheckedDiv {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone().checked_div(val2.clone()))
+}
+
+/// The remainder of dividing one expression by another, with a value of `None` instead of a panic
+/// when dividing by zero.
+pub fn checked_rem<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<Option<T>>>
+    where T: 'static + Clone + CheckedDiv {
+    ::expr::binary(lhs, rhs, |val1, val2| val1.clone().checked_rem(val2.clone()))
+}
+
+pub fn min<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + PartialOrd {
+    ::expr::binary(lhs, rhs, |val1, val2| if val2 < val1 { val2.clone() } else { val1.clone() })
+}
+
+pub fn max<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(lhs: E1, rhs: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + PartialOrd {
+    ::expr::binary(lhs, rhs, |val1, val2| if val2 > val1 { val2.clone() } else { val1.clone() })
+}
+
+/// Restrict an expression's value to the range `min..=max`.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut volume = Property::new(5);
+/// let max_volume = Property::new(10);
+/// let clamped = expr::math::clamp(&volume, 0, &max_volume);
+///
+/// volume.set(11);
+/// assert_eq!(10, clamped.get());
+/// ```
+pub fn clamp<T, E1, E2, E3>(value: E1, min: E2, max: E3) -> Rc<Expression<T>>
+    where T: 'static + Clone + PartialOrd, E1: IntoExpression<T>, E2: IntoExpression<T>, E3: IntoExpression<T> {
+    ::expr::map3(value, min, max, |val, min, max| {
+        if val < min { min.clone() } else if val > max { max.clone() } else { val.clone() }
+    })
+}
+
+pub fn pow<T, E1: IntoExpression<T>, E2: IntoExpression<u32>>(base: E1, exp: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone + Pow {
+    ::expr::binary(base, exp, |val, &exp| val.clone().pow(exp))
+}
+
+pub fn powf<T, E1: IntoExpression<T>, E2: IntoExpression<T>>(base: E1, exp: E2) -> Rc<Expression<T>>
+    where T: 'static + Float {
+    ::expr::binary(base, exp, |&val, &exp| val.powf(exp))
+}
+
+pub fn sqrt<T: 'static + Float, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>> {
+    ::expr::unary(value, |&val| val.sqrt())
+}
+
+pub fn floor<T: 'static + Float, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>> {
+    ::expr::unary(value, |&val| val.floor())
+}
+
+pub fn ceil<T: 'static + Float, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>> {
+    ::expr::unary(value, |&val| val.ceil())
+}
+
+pub fn round<T: 'static + Float, E: IntoExpression<T>>(value: E) -> Rc<Expression<T>> {
+    ::expr::unary(value, |&val| val.round())
+}
+
+/// Linearly interpolate between `from` and `to`, where a `t` of `0.0` is `from` and `1.0` is `to`.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut progress = Property::new(0.0);
+/// let width = expr::math::lerp(100.0, 300.0, &progress);
+/// assert_eq!(100.0, width.get());
+///
+/// progress.set(0.25);
+/// assert_eq!(150.0, width.get());
+/// ```
+pub fn lerp<T, E1, E2, E3>(from: E1, to: E2, t: E3) -> Rc<Expression<T>>
+    where T: 'static + Float, E1: IntoExpression<T>, E2: IntoExpression<T>, E3: IntoExpression<T> {
+    ::expr::map3(from, to, t, |&from, &to, &t| from + (to - from) * t)
 }
@@ -1,36 +1,67 @@
 pub mod cmp;
 pub mod logic;
 pub mod math;
+mod ops;
+pub mod option;
+pub mod result;
 pub mod text;
 
+pub use self::ops::Expr;
+
 // For CoreExpressions
+use std::cell::{Cell, RefCell};
 use std::cmp::PartialOrd;
 use std::fmt;
 use std::marker::Sized;
-use std::ops::{Add, Mul, Neg};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 use std::rc::Rc;
+use std::str::FromStr;
 
-use obsv::InvalidationHandler;
+use computed;
+use obsv::{ChangeHandler, DisposalHandler, InvalidationHandler, Observable, ObservablePtr};
+use weak::WeakList;
 
-pub trait IntoExpression<T: PartialEq> {
+pub trait IntoExpression<T> {
     fn into_expr(self) -> Rc<Expression<T>>;
 }
 
-impl<T: PartialEq> IntoExpression<T> for Rc<Expression<T>> {
+impl<T> IntoExpression<T> for Rc<Expression<T>> {
     fn into_expr(self) -> Rc<Expression<T>> {
         self
     }
 }
 
-pub trait Expression<T: PartialEq>: IntoExpression<T> {
+pub trait Expression<T>: IntoExpression<T> {
     fn try_get(&self) -> Option<T>;
     fn get(&self) -> T {
         self.try_get().unwrap()
     }
+    /// Register a handler which fires whenever a source this expression depends on changes.
+    /// Implementations should raise the handler's rank to this expression's `height` before
+    /// passing it on to their sources.
     fn add_invalidation_handler(&self, handler: &InvalidationHandler);
+    /// How far this expression is from the observables it ultimately reads: 0 for an expression
+    /// wrapping an observable directly, and one more than its tallest source for an expression
+    /// derived from others. This is used to fire invalidation handlers in dependency order.
+    fn height(&self) -> usize {
+        0
+    }
+    /// Register a handler which fires when a source this expression depends on is dropped, after
+    /// which `try_get` will return `None`. Expressions which don't wrap any observables are never
+    /// disposed, so by default this does nothing.
+    fn add_disposal_handler(&self, _handler: &DisposalHandler) {}
 }
 
-pub trait CoreExpressions<T: PartialEq>: IntoExpression<T> where Self: Sized {
+pub trait CoreExpressions<T>: IntoExpression<T> where Self: Sized {
+    // listening
+
+    /// Register a handler which will be called with the old and new values of this expression
+    /// whenever it changes. The handler stops listening once it is dropped.
+    fn add_change_handler(self, handler: &ChangeHandler<T>)
+        where T: 'static + PartialEq + Clone {
+        handler.watch(self.into_expr());
+    }
+
     // logic
 
     fn and<E: IntoExpression<bool>>(self, rhs: E) -> Rc<Expression<bool>>
@@ -38,7 +69,8 @@ pub trait CoreExpressions<T: PartialEq>: IntoExpression<T> where Self: Sized {
         logic::and(self, rhs)
     }
 
-    fn not(self) -> Rc<Expression<bool>>
+    /// Named so it can't be confused with `std::ops::Not`, which `Expr<bool>` also implements.
+    fn inverted(self) -> Rc<Expression<bool>>
         where Self: IntoExpression<bool> {
         logic::not(self)
     }
@@ -48,91 +80,249 @@ pub trait CoreExpressions<T: PartialEq>: IntoExpression<T> where Self: Sized {
         logic::or(self, rhs)
     }
 
+    fn xor<E: IntoExpression<bool>>(self, rhs: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<bool> {
+        logic::xor(self, rhs)
+    }
+
+    fn nand<E: IntoExpression<bool>>(self, rhs: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<bool> {
+        logic::nand(self, rhs)
+    }
+
+    fn implies<E: IntoExpression<bool>>(self, rhs: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<bool> {
+        logic::implies(self, rhs)
+    }
+
+    /// Pick between two expressions, based on this one's value. See `expr::when`.
+    fn then_else<U, E1: IntoExpression<U>, E2: IntoExpression<U>>(self, if_true: E1, if_false: E2) -> Rc<Expression<U>>
+        where U: 'static + Clone, Self: IntoExpression<bool> {
+        when(self, if_true, if_false)
+    }
+
+    /// Follow whichever expression `f` returns for this expression's current value. See
+    /// `expr::switch_map`.
+    fn switch_map<U, F>(self, f: F) -> Rc<Expression<U>>
+        where T: 'static, U: 'static, F: 'static + Fn(&T) -> Rc<Expression<U>> {
+        switch_map(self, f)
+    }
 
     // math
 
     fn abs(self) -> Rc<Expression<T>>
-        where T: 'static + Copy + PartialOrd + Default + Neg<Output=T>, Self: IntoExpression<T> {
+        where T: 'static + Clone + PartialOrd + Default + Neg<Output=T>, Self: IntoExpression<T> {
         math::abs(self)
     }
 
-    fn neg(self) -> Rc<Expression<T>>
-        where T: 'static + Copy + Neg<Output=T>, Self: IntoExpression<T> {
+    /// Named so it can't be confused with `std::ops::Neg`, which `Expr<T>` also implements.
+    fn negated(self) -> Rc<Expression<T>>
+        where T: 'static + Clone + Neg<Output=T>, Self: IntoExpression<T> {
         math::neg(self)
     }
 
     fn plus<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
-        where T: 'static + Copy + Add<Output=T> {
+        where T: 'static + Clone + Add<Output=T> {
         math::plus(self, rhs)
     }
 
     fn times<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
-        where T: 'static + Copy + Mul<Output=T> {
+        where T: 'static + Clone + Mul<Output=T> {
         math::times(self, rhs)
     }
 
+    fn minus<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + Sub<Output=T> {
+        math::minus(self, rhs)
+    }
+
+    /// Named so it can't be confused with `std::ops::Div`, which `Expr<T>` also implements.
+    fn divided_by<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + Div<Output=T> {
+        math::div(self, rhs)
+    }
+
+    fn rem<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + Rem<Output=T> {
+        math::rem(self, rhs)
+    }
+
+    fn checked_div<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<Option<T>>>
+        where T: 'static + Clone + math::CheckedDiv {
+        math::checked_div(self, rhs)
+    }
+
+    fn checked_rem<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<Option<T>>>
+        where T: 'static + Clone + math::CheckedDiv {
+        math::checked_rem(self, rhs)
+    }
+
+    fn min<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + PartialOrd {
+        math::min(self, rhs)
+    }
+
+    fn max<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + PartialOrd {
+        math::max(self, rhs)
+    }
+
+    fn clamp<E1: IntoExpression<T>, E2: IntoExpression<T>>(self, min: E1, max: E2) -> Rc<Expression<T>>
+        where T: 'static + Clone + PartialOrd {
+        math::clamp(self, min, max)
+    }
+
+    fn pow<E: IntoExpression<u32>>(self, exp: E) -> Rc<Expression<T>>
+        where T: 'static + Clone + math::Pow {
+        math::pow(self, exp)
+    }
+
+    fn powf<E: IntoExpression<T>>(self, exp: E) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::powf(self, exp)
+    }
+
+    fn sqrt(self) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::sqrt(self)
+    }
+
+    fn floor(self) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::floor(self)
+    }
+
+    fn ceil(self) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::ceil(self)
+    }
+
+    fn round(self) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::round(self)
+    }
+
+    fn lerp<E1: IntoExpression<T>, E2: IntoExpression<T>>(self, to: E1, t: E2) -> Rc<Expression<T>>
+        where T: 'static + math::Float {
+        math::lerp(self, to, t)
+    }
+
     // cmp
 
     fn eq<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq, Self: IntoExpression<T> {
         cmp::eq(self, rhs)
     }
 
     fn eq_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq, Self: IntoExpression<T> {
         cmp::eq_val(self, val)
     }
 
     fn ne<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq, Self: IntoExpression<T> {
         cmp::ne(self, rhs)
     }
 
     fn ne_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq, Self: IntoExpression<T> {
         cmp::ne_val(self, val)
     }
 
     fn gt<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::gt(self, rhs)
     }
 
     fn gt_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::gt_val(self, val)
     }
 
     fn lt<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::lt(self, rhs)
     }
 
     fn lt_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::lt_val(self, val)
     }
 
     fn gte<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::gte(self, rhs)
     }
 
     fn gte_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::gte_val(self, val)
     }
 
     fn lte<E: IntoExpression<T>>(self, rhs: E) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::lte(self, rhs)
     }
 
     fn lte_val(self, val: T) -> Rc<Expression<bool>>
-        where T: 'static + PartialEq + Copy + PartialOrd, Self: IntoExpression<T> {
+        where T: 'static + PartialEq + PartialOrd, Self: IntoExpression<T> {
         cmp::lte_val(self, val)
     }
 
+    // option
+
+    fn is_some<U: 'static>(self) -> Rc<Expression<bool>>
+        where Self: IntoExpression<Option<U>> {
+        option::is_some(self)
+    }
+
+    fn is_none<U: 'static>(self) -> Rc<Expression<bool>>
+        where Self: IntoExpression<Option<U>> {
+        option::is_none(self)
+    }
+
+    fn unwrap_or<U: 'static + Clone>(self, default: U) -> Rc<Expression<U>>
+        where Self: IntoExpression<Option<U>> {
+        option::unwrap_or(self, default)
+    }
+
+    fn unwrap_or_expr<U: 'static + Clone, E: IntoExpression<U>>(self, other: E) -> Rc<Expression<U>>
+        where Self: IntoExpression<Option<U>> {
+        option::unwrap_or_expr(self, other)
+    }
+
+    fn map_some<U: 'static, V: 'static + Clone, F: 'static + Fn(&U) -> V>(self, f: F) -> Rc<Expression<Option<V>>>
+        where Self: IntoExpression<Option<U>> {
+        option::map_some(self, f)
+    }
+
+    fn and_then<U: 'static, V: 'static + Clone, F: 'static + Fn(&U) -> Option<V>>(self, f: F) -> Rc<Expression<Option<V>>>
+        where Self: IntoExpression<Option<U>> {
+        option::and_then(self, f)
+    }
+
+    // result
+
+    fn ok<U: 'static + Clone, Err: 'static>(self) -> Rc<Expression<Option<U>>>
+        where Self: IntoExpression<Result<U, Err>> {
+        result::ok(self)
+    }
+
+    fn is_ok<U: 'static, Err: 'static>(self) -> Rc<Expression<bool>>
+        where Self: IntoExpression<Result<U, Err>> {
+        result::is_ok(self)
+    }
+
+    fn is_err<U: 'static, Err: 'static>(self) -> Rc<Expression<bool>>
+        where Self: IntoExpression<Result<U, Err>> {
+        result::is_err(self)
+    }
+
+    fn err_message<U: 'static, Err: 'static + fmt::Display>(self) -> Rc<Expression<Option<String>>>
+        where Self: IntoExpression<Result<U, Err>> {
+        result::err_message(self)
+    }
+
     // text
 
     fn len(self) -> Rc<Expression<usize>>
@@ -154,72 +344,670 @@ pub trait CoreExpressions<T: PartialEq>: IntoExpression<T> where Self: Sized {
         where T: 'static + fmt::Display {
         text::to_string(self)
     }
+
+    fn char_count(self) -> Rc<Expression<usize>>
+        where Self: IntoExpression<String> {
+        text::char_count(self)
+    }
+
+    fn concat<E: IntoExpression<String>>(self, rhs: E) -> Rc<Expression<String>>
+        where Self: IntoExpression<String> {
+        text::concat(self, rhs)
+    }
+
+    fn contains<E: IntoExpression<String>>(self, pattern: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<String> {
+        text::contains(self, pattern)
+    }
+
+    fn starts_with<E: IntoExpression<String>>(self, pattern: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<String> {
+        text::starts_with(self, pattern)
+    }
+
+    fn ends_with<E: IntoExpression<String>>(self, pattern: E) -> Rc<Expression<bool>>
+        where Self: IntoExpression<String> {
+        text::ends_with(self, pattern)
+    }
+
+    fn to_uppercase(self) -> Rc<Expression<String>>
+        where Self: IntoExpression<String> {
+        text::to_uppercase(self)
+    }
+
+    fn to_lowercase(self) -> Rc<Expression<String>>
+        where Self: IntoExpression<String> {
+        text::to_lowercase(self)
+    }
+
+    fn replace<E1: IntoExpression<String>, E2: IntoExpression<String>>(self, from: E1, to: E2) -> Rc<Expression<String>>
+        where Self: IntoExpression<String> {
+        text::replace(self, from, to)
+    }
+
+    fn substring<E1: IntoExpression<usize>, E2: IntoExpression<usize>>(self, start: E1, end: E2) -> Rc<Expression<String>>
+        where Self: IntoExpression<String> {
+        text::substring(self, start, end)
+    }
+
+    fn split<E: IntoExpression<String>>(self, separator: E) -> Rc<Expression<Vec<String>>>
+        where Self: IntoExpression<String> {
+        text::split(self, separator)
+    }
+
+    fn parse<U>(self) -> Rc<Expression<Result<U, String>>>
+        where U: 'static + Clone + FromStr, U::Err: fmt::Display, Self: IntoExpression<String> {
+        text::parse(self)
+    }
+}
+
+impl<T> CoreExpressions<T> for Rc<Expression<T>> {
+    // Default implementation is fine
+}
+
+/// Create an expression which always has the same value. This allows plain values to be passed
+/// anywhere an expression is expected.
+///
+/// Common literals (numbers, `bool`, `char` and strings) can already be used as expressions
+/// directly, so this is mostly useful for other types.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut score = Property::new(10);
+/// let boosted = score.plus(5).times(2); // Literals work directly
+/// let passing = score.gte(expr::constant(15));
+/// assert_eq!(30, boosted.get());
+/// assert_eq!(false, passing.get());
+///
+/// score.set(20);
+/// assert_eq!(50, boosted.get());
+/// assert_eq!(true, passing.get());
+/// ```
+pub fn constant<T: 'static + Clone>(value: T) -> Rc<Expression<T>> {
+    Rc::new(Const(value))
+}
+
+/// An expression which always has the same value. See `constant`.
+pub struct Const<T>(pub T);
+
+impl<T: 'static + Clone> IntoExpression<T> for Const<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        Rc::new(self)
+    }
+}
+
+impl<T: 'static + Clone> Expression<T> for Const<T> {
+    fn try_get(&self) -> Option<T> {
+        Some(self.0.clone())
+    }
+
+    fn add_invalidation_handler(&self, _handler: &InvalidationHandler) {
+        // Never changes
+    }
 }
 
-impl<T: PartialEq> CoreExpressions<T> for Rc<Expression<T>> {
+impl<T: 'static + Clone> CoreExpressions<T> for Const<T> {
     // Default implementation is fine
 }
 
+/// Lets literals of common types be used as constant expressions directly.
+macro_rules! literal_exprs {
+    ($($t:ty),+) => {
+        $(
+            impl IntoExpression<$t> for $t {
+                fn into_expr(self) -> Rc<Expression<$t>> {
+                    constant(self)
+                }
+            }
+        )+
+    }
+}
+
+literal_exprs!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char, String);
+
+impl IntoExpression<String> for &'static str {
+    fn into_expr(self) -> Rc<Expression<String>> {
+        constant(self.to_string())
+    }
+}
+
+/// Create an expression which applies `f` to the value of `src`.
+///
+/// The result is cached, and `f` only runs again the next time the expression is read after `src`
+/// changed. Cached values are handed out by cloning them, so `O` must be `Clone`. For outputs that
+/// are expensive to clone, wrap them in an `Rc`.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut name = Property::new(String::from("world"));
+/// let greeting = expr::unary(&name, |name| format!("Hello, {}!", name));
+/// assert_eq!("Hello, world!", greeting.get());
+///
+/// name.set(String::from("Jane"));
+/// assert_eq!("Hello, Jane!", greeting.get());
+/// ```
 pub fn unary<I, O, E, F>(src: E, f: F) -> Rc<Expression<O>>
-    where I: 'static + PartialEq, O: 'static + PartialEq, E: IntoExpression<I>, F: 'static + Fn(&I) -> O {
-    UnaryExpression {
+    where I: 'static, O: 'static + Clone, E: IntoExpression<I>, F: 'static + Fn(&I) -> O {
+    let expr = UnaryExpression {
         src: src.into_expr(),
         f: Box::new(f),
-    }.into_expr()
+        cache: Cache::new(),
+    };
+    expr.add_invalidation_handler(&expr.cache.handle);
+    expr.add_disposal_handler(&expr.cache.disposal_handle);
+    expr.into_expr()
 }
 
+/// Create an expression which applies `f` to the values of `lhs` and `rhs`. Like `unary`, the
+/// result is cached and handed out by cloning it, so `O` must be `Clone`.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut width = Property::new(4);
+/// let height = Property::new(3);
+/// let area = expr::binary(&width, &height, |w, h| w * h);
+/// assert_eq!(12, area.get());
+///
+/// width.set(5);
+/// assert_eq!(15, area.get());
+/// ```
 pub fn binary<I1, I2, O, E1, E2, F>(lhs: E1, rhs: E2, f: F) -> Rc<Expression<O>>
-    where I1: 'static + PartialEq, I2: 'static + PartialEq, O: 'static + PartialEq, E1: IntoExpression<I1>, E2: IntoExpression<I2>, F: 'static + Fn(&I1, &I2) -> O {
-    BinaryExpression {
+    where I1: 'static, I2: 'static, O: 'static + Clone, E1: IntoExpression<I1>, E2: IntoExpression<I2>, F: 'static + Fn(&I1, &I2) -> O {
+    let expr = BinaryExpression {
         lhs: lhs.into_expr(),
         rhs: rhs.into_expr(),
         f: Box::new(f),
-    }.into_expr()
+        cache: Cache::new(),
+    };
+    expr.add_invalidation_handler(&expr.cache.handle);
+    expr.add_disposal_handler(&expr.cache.disposal_handle);
+    expr.into_expr()
+}
+
+/// Create an expression which takes the value of `if_true` or `if_false`, depending on the value
+/// of `cond`. Only the chosen branch is evaluated, but the expression is invalidated when the
+/// condition or either branch changes.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut use_nickname = Property::new(false);
+/// let name = Property::new(String::from("Robert"));
+/// let nickname = Property::new(String::from("Bob"));
+/// let display_name = expr::when(&use_nickname, &nickname, &name);
+/// assert_eq!("Robert", display_name.get());
+///
+/// use_nickname.set(true);
+/// assert_eq!("Bob", display_name.get());
+/// ```
+pub fn when<T, C, E1, E2>(cond: C, if_true: E1, if_false: E2) -> Rc<Expression<T>>
+    where T: 'static + Clone, C: IntoExpression<bool>, E1: IntoExpression<T>, E2: IntoExpression<T> {
+    let expr = ConditionalExpression {
+        cond: cond.into_expr(),
+        if_true: if_true.into_expr(),
+        if_false: if_false.into_expr(),
+        cache: Cache::new(),
+    };
+    expr.add_invalidation_handler(&expr.cache.handle);
+    expr.add_disposal_handler(&expr.cache.disposal_handle);
+    expr.into_expr()
+}
+
+/// Create an expression which follows whichever expression `f` returns for the current value of
+/// `src`. Whenever `src` changes, `f` is called again and the expression stops listening to the old
+/// inner expression and starts listening to the new one.
+///
+/// This is useful when a value is itself something with properties, such as the currently selected
+/// item in a list. Disposal handlers hear about `src` being dropped, as well as the inner
+/// expression currently being followed.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use pebl::prelude::*;
+/// use pebl::expr::IntoExpression;
+///
+/// let mut titles = vec![Property::new(String::from("Notes")), Property::new(String::from("Todo"))];
+/// let title_exprs: Vec<Rc<Expression<String>>> = titles.iter().map(|title| title.into_expr()).collect();
+/// let mut selected = Property::new(0);
+/// let selected_title = selected.switch_map(move |&i| title_exprs[i].clone());
+/// assert_eq!("Notes", selected_title.get());
+///
+/// selected.set(1);
+/// assert_eq!("Todo", selected_title.get());
+///
+/// titles[1].set(String::from("Groceries"));
+/// assert_eq!("Groceries", selected_title.get());
+/// ```
+pub fn switch_map<A, B, E, F>(src: E, f: F) -> Rc<Expression<B>>
+    where A: 'static, B: 'static, E: IntoExpression<A>, F: 'static + Fn(&A) -> Rc<Expression<B>> {
+    let state = Rc::new(SwitchState {
+        outer: src.into_expr(),
+        f: Box::new(f),
+        inner: RefCell::new(None),
+        inner_handle: RefCell::new(None),
+        outer_handle: RefCell::new(None),
+        notifier: Observable::new(()),
+        on_disposed: Rc::new(RefCell::new(WeakList::new())),
+        inner_disposal_handle: RefCell::new(None),
+    });
+
+    let weak_state = Rc::downgrade(&state);
+    let outer_handle = InvalidationHandler::new(move || {
+        if let Some(state) = weak_state.upgrade() {
+            state.switch();
+            ObservablePtr::new(&state.notifier).deref().invalidate();
+        }
+    });
+    state.outer.add_invalidation_handler(&outer_handle);
+    *state.outer_handle.borrow_mut() = Some(outer_handle);
+    state.switch();
+
+    Rc::new(SwitchExpression { state: state })
+}
+
+/// Generates a `mapN` function, which combines the values of N source expressions at once.
+macro_rules! map_fn {
+    ($(#[$attr:meta])* fn $name:ident($(($src:ident: $E:ident -> $I:ident)),+)) => {
+        $(#[$attr])*
+        #[allow(clippy::too_many_arguments)] // One per source, by design
+        pub fn $name<$($I,)+ O, $($E,)+ F>($($src: $E,)+ f: F) -> Rc<Expression<O>>
+            where $($I: 'static,)+ O: 'static + Clone, $($E: IntoExpression<$I>,)+ F: 'static + Fn($(&$I),+) -> O {
+            $(let $src = $src.into_expr();)+
+            let sources: Vec<Box<Source>> = vec![$(Box::new($src.clone())),+];
+            nary(sources, move || match ($($src.try_get(),)+) {
+                ($(Some($src),)+) => Some(f($(&$src),+)),
+                _ => None,
+            })
+        }
+    }
+}
+
+map_fn! {
+    /// Create an expression which combines the values of three source expressions, like `binary`
+    /// does for two. The expression's value is `None` if any of its sources were dropped.
+    ///
+    /// Functions up to `map8` are also available for combining more sources.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pebl::prelude::*;
+    ///
+    /// let mut first = Property::new(String::from("Jane"));
+    /// let middle = Property::new('Q');
+    /// let last = Property::new(String::from("Public"));
+    /// let full_name = expr::map3(&first, &middle, &last, |f, m, l| format!("{} {}. {}", f, m, l));
+    /// assert_eq!("Jane Q. Public", full_name.get());
+    ///
+    /// first.set(String::from("John"));
+    /// assert_eq!("John Q. Public", full_name.get());
+    /// ```
+    fn map3((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3))
+}
+
+map_fn! {
+    /// Create an expression which combines the values of four source expressions. See `map3`.
+    fn map4((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3), (e4: E4 -> I4))
+}
+
+map_fn! {
+    /// Create an expression which combines the values of five source expressions. See `map3`.
+    fn map5((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3), (e4: E4 -> I4), (e5: E5 -> I5))
+}
+
+map_fn! {
+    /// Create an expression which combines the values of six source expressions. See `map3`.
+    fn map6((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3), (e4: E4 -> I4), (e5: E5 -> I5), (e6: E6 -> I6))
+}
+
+map_fn! {
+    /// Create an expression which combines the values of seven source expressions. See `map3`.
+    fn map7((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3), (e4: E4 -> I4), (e5: E5 -> I5), (e6: E6 -> I6), (e7: E7 -> I7))
+}
+
+map_fn! {
+    /// Create an expression which combines the values of eight source expressions. See `map3`.
+    fn map8((e1: E1 -> I1), (e2: E2 -> I2), (e3: E3 -> I3), (e4: E4 -> I4), (e5: E5 -> I5), (e6: E6 -> I6), (e7: E7 -> I7), (e8: E8 -> I8))
+}
+
+/// Create an expression which collects the values of any number of source expressions into a
+/// `Vec`, in order. The expression's value is `None` if any of its sources were dropped.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+/// use pebl::expr::IntoExpression;
+///
+/// let mut scores = vec![Property::new(10), Property::new(20), Property::new(30)];
+/// let all = expr::combine_vec(scores.iter().map(|score| score.into_expr()).collect());
+/// let total = expr::unary(all.clone(), |all| all.iter().sum::<i32>());
+/// assert_eq!(vec![10, 20, 30], all.get());
+///
+/// scores[1].set(25);
+/// assert_eq!(65, total.get());
+/// ```
+pub fn combine_vec<T: 'static + Clone>(srcs: Vec<Rc<Expression<T>>>) -> Rc<Expression<Vec<T>>> {
+    let sources: Vec<Box<Source>> = srcs.iter().map(|src| Box::new(src.clone()) as Box<Source>).collect();
+    nary(sources, move || srcs.iter().map(|src| src.try_get()).collect())
+}
+
+fn nary<O: 'static + Clone, F: 'static + Fn() -> Option<O>>(sources: Vec<Box<Source>>, f: F) -> Rc<Expression<O>> {
+    let expr = NaryExpression {
+        sources: sources,
+        f: Box::new(f),
+        cache: Cache::new(),
+    };
+    expr.add_invalidation_handler(&expr.cache.handle);
+    expr.add_disposal_handler(&expr.cache.disposal_handle);
+    expr.into_expr()
+}
+
+/// The last value computed by a derived expression, which is reused until one of its sources is
+/// invalidated.
+///
+/// Invalidations can be deferred by a batch, be queued behind the handler that is currently
+/// firing, or be dropped entirely if dispatching is cut short. Rather than waiting for its handler
+/// to fire, the cache checks whether the handler was queued since the value was computed.
+struct Cache<O> {
+    value: RefCell<Option<O>>,
+    // Set once any source is dropped, after which the value can't be trusted anymore
+    disposed: Rc<Cell<bool>>,
+    handle: InvalidationHandler,
+    disposal_handle: DisposalHandler,
 }
 
-pub struct UnaryExpression<I: 'static + PartialEq, O: 'static + PartialEq> {
+impl<O: Clone> Cache<O> {
+    fn new() -> Cache<O> {
+        let disposed = Rc::new(Cell::new(false));
+        // Being queued is what marks the cache dirty, so there is nothing left to do once fired
+        let handle = InvalidationHandler::new(|| {});
+        let disposal_handle;
+        {
+            let disposed = disposed.clone();
+            disposal_handle = DisposalHandler::new(move || disposed.set(true));
+        }
+        Cache { value: RefCell::new(None), disposed: disposed, handle: handle, disposal_handle: disposal_handle }
+    }
+
+    /// Return the cached value if it's still valid, or call `compute` to get a new one.
+    fn get_or_compute<F: FnOnce() -> Option<O>>(&self, compute: F) -> Option<O> {
+        // While a computed expression is being evaluated, it needs to see every source we read
+        if self.disposed.get() || computed::is_tracking() {
+            return compute();
+        }
+        if !self.handle.is_dirty() {
+            if let Some(ref value) = *self.value.borrow() {
+                return Some(value.clone());
+            }
+        }
+
+        // Mark the cache clean first, so any invalidation raised while computing isn't lost
+        self.handle.mark_clean();
+        let value = compute();
+        *self.value.borrow_mut() = value.clone();
+        value
+    }
+}
+
+pub struct UnaryExpression<I: 'static, O: 'static> {
     src: Rc<Expression<I>>,
     f: Box<Fn(&I) -> O>,
+    cache: Cache<O>,
 }
 
-impl<I: 'static + PartialEq, O: 'static + PartialEq> IntoExpression<O> for UnaryExpression<I, O> {
+impl<I: 'static, O: 'static + Clone> IntoExpression<O> for UnaryExpression<I, O> {
     fn into_expr(self) -> Rc<Expression<O>> {
         Rc::new(self)
     }
 }
 
-impl<I: 'static + PartialEq, O: 'static + PartialEq> Expression<O> for UnaryExpression<I, O> {
+impl<I: 'static, O: 'static + Clone> Expression<O> for UnaryExpression<I, O> {
     fn try_get(&self) -> Option<O> {
-        self.src.try_get().map(|val| (self.f)(&val))
+        self.cache.get_or_compute(|| self.src.try_get().map(|val| (self.f)(&val)))
     }
 
     fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
         self.src.add_invalidation_handler(handler);
     }
+
+    fn height(&self) -> usize {
+        self.src.height() + 1
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        self.src.add_disposal_handler(handler);
+    }
 }
 
-pub struct BinaryExpression<I1: 'static + PartialEq, I2: 'static + PartialEq, O: 'static + PartialEq> {
+pub struct BinaryExpression<I1: 'static, I2: 'static, O: 'static> {
     lhs: Rc<Expression<I1>>,
     rhs: Rc<Expression<I2>>,
     f: Box<Fn(&I1, &I2) -> O>,
+    cache: Cache<O>,
 }
 
-impl<I1: 'static + PartialEq, I2: 'static + PartialEq, O: 'static + PartialEq> IntoExpression<O> for BinaryExpression<I1, I2, O> {
+impl<I1: 'static, I2: 'static, O: 'static + Clone> IntoExpression<O> for BinaryExpression<I1, I2, O> {
     fn into_expr(self) -> Rc<Expression<O>> {
         Rc::new(self)
     }
 }
 
-impl<I1: 'static + PartialEq, I2: 'static + PartialEq, O: 'static + PartialEq> Expression<O> for BinaryExpression<I1, I2, O> {
+impl<I1: 'static, I2: 'static, O: 'static + Clone> Expression<O> for BinaryExpression<I1, I2, O> {
     fn try_get(&self) -> Option<O> {
-        let (lhs_opt, rhs_opt) = (self.lhs.try_get(), self.rhs.try_get());
-        if lhs_opt.is_none() || rhs_opt.is_none() { return None }
-        Some((self.f)(&lhs_opt.unwrap(), &rhs_opt.unwrap()))
+        self.cache.get_or_compute(|| {
+            let (lhs_opt, rhs_opt) = (self.lhs.try_get(), self.rhs.try_get());
+            if lhs_opt.is_none() || rhs_opt.is_none() { return None }
+            Some((self.f)(&lhs_opt.unwrap(), &rhs_opt.unwrap()))
+        })
     }
 
     fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
         self.lhs.add_invalidation_handler(handler);
         self.rhs.add_invalidation_handler(handler);
     }
+
+    fn height(&self) -> usize {
+        self.lhs.height().max(self.rhs.height()) + 1
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        self.lhs.add_disposal_handler(handler);
+        self.rhs.add_disposal_handler(handler);
+    }
+}
+
+/// A source expression with its value type erased, so that expressions with sources of many
+/// different types can hold onto them in a single list.
+trait Source {
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler);
+    fn add_disposal_handler(&self, handler: &DisposalHandler);
+    fn height(&self) -> usize;
 }
 
+impl<T> Source for Rc<Expression<T>> {
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        Expression::add_invalidation_handler(&**self, handler);
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        Expression::add_disposal_handler(&**self, handler);
+    }
+
+    fn height(&self) -> usize {
+        Expression::height(&**self)
+    }
+}
+
+pub struct NaryExpression<O: 'static> {
+    sources: Vec<Box<Source>>,
+    f: Box<Fn() -> Option<O>>,
+    cache: Cache<O>,
+}
+
+impl<O: 'static + Clone> IntoExpression<O> for NaryExpression<O> {
+    fn into_expr(self) -> Rc<Expression<O>> {
+        Rc::new(self)
+    }
+}
+
+impl<O: 'static + Clone> Expression<O> for NaryExpression<O> {
+    fn try_get(&self) -> Option<O> {
+        self.cache.get_or_compute(|| (self.f)())
+    }
+
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
+        for source in &self.sources {
+            source.add_invalidation_handler(handler);
+        }
+    }
+
+    fn height(&self) -> usize {
+        self.sources.iter().map(|source| source.height()).max().unwrap_or(0) + 1
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        for source in &self.sources {
+            source.add_disposal_handler(handler);
+        }
+    }
+}
+
+pub struct ConditionalExpression<T: 'static> {
+    cond: Rc<Expression<bool>>,
+    if_true: Rc<Expression<T>>,
+    if_false: Rc<Expression<T>>,
+    cache: Cache<T>,
+}
+
+impl<T: 'static + Clone> IntoExpression<T> for ConditionalExpression<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        Rc::new(self)
+    }
+}
+
+impl<T: 'static + Clone> Expression<T> for ConditionalExpression<T> {
+    fn try_get(&self) -> Option<T> {
+        self.cache.get_or_compute(|| {
+            self.cond.try_get().and_then(|cond| if cond { self.if_true.try_get() } else { self.if_false.try_get() })
+        })
+    }
+
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
+        self.cond.add_invalidation_handler(handler);
+        self.if_true.add_invalidation_handler(handler);
+        self.if_false.add_invalidation_handler(handler);
+    }
+
+    fn height(&self) -> usize {
+        self.cond.height().max(self.if_true.height()).max(self.if_false.height()) + 1
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        // Either branch may end up being chosen, so losing either one counts
+        self.cond.add_disposal_handler(handler);
+        self.if_true.add_disposal_handler(handler);
+        self.if_false.add_disposal_handler(handler);
+    }
+}
+
+type SwitchFn<A, B> = Box<Fn(&A) -> Rc<Expression<B>>>;
+
+struct SwitchState<A: 'static, B: 'static> {
+    outer: Rc<Expression<A>>,
+    f: SwitchFn<A, B>,
+    inner: RefCell<Option<Rc<Expression<B>>>>,
+    // Replaced whenever we move to a new inner expression, which stops listening to the old one
+    inner_handle: RefCell<Option<InvalidationHandler>>,
+    outer_handle: RefCell<Option<InvalidationHandler>>,
+    // Fired when either the outer or inner expression changes, to let our listeners know
+    notifier: Observable<()>,
+    // Disposal handlers registered with us, which also need to hear about the current inner
+    // expression being disposed
+    on_disposed: Rc<RefCell<WeakList<Fn()>>>,
+    inner_disposal_handle: RefCell<Option<DisposalHandler>>,
+}
+
+impl<A: 'static, B: 'static> SwitchState<A, B> {
+    /// Move our subscription to the inner expression for the outer expression's current value.
+    fn switch(&self) {
+        if let Some(ref handle) = *self.outer_handle.borrow() {
+            handle.mark_clean();
+        }
+        let inner = self.outer.try_get().map(|outer| (self.f)(&outer));
+        let inner_handle = inner.as_ref().map(|inner| {
+            let notifier = ObservablePtr::new(&self.notifier);
+            let handle = InvalidationHandler::new(move || notifier.deref().invalidate());
+            inner.add_invalidation_handler(&handle);
+            handle
+        });
+        let inner_disposal_handle = inner.as_ref().map(|inner| {
+            let on_disposed = self.on_disposed.clone();
+            let handle = DisposalHandler::new(move || {
+                for callback in on_disposed.borrow().upgrade() {
+                    callback();
+                }
+            });
+            inner.add_disposal_handler(&handle);
+            handle
+        });
+        *self.inner.borrow_mut() = inner;
+        *self.inner_handle.borrow_mut() = inner_handle;
+        *self.inner_disposal_handle.borrow_mut() = inner_disposal_handle;
+    }
+}
+
+pub struct SwitchExpression<A: 'static, B: 'static> {
+    state: Rc<SwitchState<A, B>>,
+}
+
+impl<A: 'static, B: 'static> IntoExpression<B> for SwitchExpression<A, B> {
+    fn into_expr(self) -> Rc<Expression<B>> {
+        Rc::new(self)
+    }
+}
+
+impl<A: 'static, B: 'static> Expression<B> for SwitchExpression<A, B> {
+    fn try_get(&self) -> Option<B> {
+        // Our subscription is behind while the outer expression's invalidation is still waiting
+        // to be fired, and computed expressions need to see the outer expression being read
+        let outer_dirty = self.state.outer_handle.borrow().as_ref().is_some_and(|handle| handle.is_dirty());
+        if outer_dirty || computed::is_tracking() {
+            return self.state.outer.try_get().and_then(|outer| (self.state.f)(&outer).try_get());
+        }
+        let inner = self.state.inner.borrow().clone();
+        inner.and_then(|inner| inner.try_get())
+    }
+
+    fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
+        self.state.notifier.add_invalidation_handler(handler);
+    }
+
+    fn height(&self) -> usize {
+        let inner_height = self.state.inner.borrow().as_ref().map_or(0, |inner| inner.height());
+        self.state.outer.height().max(inner_height) + 1
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        self.state.outer.add_disposal_handler(handler);
+        self.state.on_disposed.borrow_mut().push(handler.callback());
+    }
+}
@@ -0,0 +1,202 @@
+use std::ops::{Add, BitAnd, BitOr, Deref, Div, Mul, Neg, Not, Sub};
+use std::rc::Rc;
+
+use super::*;
+use property::{Property, ReadOnlyProperty};
+
+/// A wrapper around an expression which supports the arithmetic and logical operators, so
+/// expressions can be written as `&price * &qty + &tax` instead of `price.times(&qty).plus(&tax)`.
+///
+/// Using an operator on a `&Property<T>` (or another `Expr<T>`) produces an `Expr<T>`, so usually
+/// you don't need to create one yourself. An existing expression can be wrapped using `Expr::new`.
+/// Either side of an operator can be a plain value, such as a numeric literal. When the number is
+/// on the left, its type may need to be spelled out, as in `2i32 * &price`.
+///
+/// `Expr<T>` derefs to `Expression<T>`, and can be passed anywhere an expression is expected.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut price = Property::new(10);
+/// let qty = Property::new(3);
+/// let tax = Property::new(2);
+/// let total = &price * &qty + &tax;
+/// let discounted = (total.clone() - 5) * 2;
+/// assert_eq!(32, total.get());
+/// assert_eq!(54, discounted.get());
+///
+/// price.set(20);
+/// assert_eq!(62, total.get());
+///
+/// let mut enabled = Property::new(true);
+/// let visible = Property::new(true);
+/// let hidden = !&enabled | !&visible;
+/// assert_eq!(false, hidden.get());
+///
+/// enabled.set(false);
+/// assert_eq!(true, hidden.get());
+/// ```
+pub struct Expr<T>(pub Rc<Expression<T>>);
+
+impl<T> Expr<T> {
+    pub fn new<E: IntoExpression<T>>(expr: E) -> Expr<T> {
+        Expr(expr.into_expr())
+    }
+}
+
+impl<T> Clone for Expr<T> {
+    fn clone(&self) -> Self {
+        Expr(self.0.clone())
+    }
+}
+
+impl<T> Deref for Expr<T> {
+    type Target = Expression<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl<T> From<Rc<Expression<T>>> for Expr<T> {
+    fn from(expr: Rc<Expression<T>>) -> Self {
+        Expr(expr)
+    }
+}
+
+impl<T> IntoExpression<T> for Expr<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        self.0
+    }
+}
+
+impl<'a, T> IntoExpression<T> for &'a Expr<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        self.0.clone()
+    }
+}
+
+impl<T> CoreExpressions<T> for Expr<T> {
+    // Default implementation is fine
+}
+
+/// Implements the operators for expression-like types, delegating to `expr::math` and
+/// `expr::logic`.
+macro_rules! expr_ops {
+    ($({$($g:tt)*} $lhs:ty),+) => {
+        $(
+            impl<$($g)*, R: IntoExpression<T>> Add<R> for $lhs where T: 'static + Clone + Add<Output=T> {
+                type Output = Expr<T>;
+                fn add(self, rhs: R) -> Expr<T> {
+                    Expr(math::plus(self, rhs))
+                }
+            }
+
+            impl<$($g)*, R: IntoExpression<T>> Sub<R> for $lhs where T: 'static + Clone + Sub<Output=T> {
+                type Output = Expr<T>;
+                fn sub(self, rhs: R) -> Expr<T> {
+                    Expr(math::minus(self, rhs))
+                }
+            }
+
+            impl<$($g)*, R: IntoExpression<T>> Mul<R> for $lhs where T: 'static + Clone + Mul<Output=T> {
+                type Output = Expr<T>;
+                fn mul(self, rhs: R) -> Expr<T> {
+                    Expr(math::times(self, rhs))
+                }
+            }
+
+            impl<$($g)*, R: IntoExpression<T>> Div<R> for $lhs where T: 'static + Clone + Div<Output=T> {
+                type Output = Expr<T>;
+                fn div(self, rhs: R) -> Expr<T> {
+                    Expr(math::div(self, rhs))
+                }
+            }
+
+            impl<$($g)*> Neg for $lhs where T: 'static + Clone + Neg<Output=T> {
+                type Output = Expr<T>;
+                fn neg(self) -> Expr<T> {
+                    Expr(math::neg(self))
+                }
+            }
+        )+
+    }
+}
+
+expr_ops!({T} Expr<T>, {'a, T} &'a Expr<T>, {'a, T} &'a Property<T>, {T} ReadOnlyProperty<T>, {'a, T} &'a ReadOnlyProperty<T>);
+
+/// Implements the logical operators for boolean expression-like types.
+macro_rules! bool_ops {
+    ($({$($g:tt)*} $lhs:ty),+) => {
+        $(
+            impl<$($g)* R: IntoExpression<bool>> BitAnd<R> for $lhs {
+                type Output = Expr<bool>;
+                fn bitand(self, rhs: R) -> Expr<bool> {
+                    Expr(logic::and(self, rhs))
+                }
+            }
+
+            impl<$($g)* R: IntoExpression<bool>> BitOr<R> for $lhs {
+                type Output = Expr<bool>;
+                fn bitor(self, rhs: R) -> Expr<bool> {
+                    Expr(logic::or(self, rhs))
+                }
+            }
+
+            impl<$($g)*> Not for $lhs {
+                type Output = Expr<bool>;
+                fn not(self) -> Expr<bool> {
+                    Expr(logic::not(self))
+                }
+            }
+        )+
+    }
+}
+
+bool_ops!({} Expr<bool>, {'a,} &'a Expr<bool>, {'a,} &'a Property<bool>, {} ReadOnlyProperty<bool>, {'a,} &'a ReadOnlyProperty<bool>);
+
+/// Lets plain numbers appear on the left hand side of an operator, as in `2 * &price`.
+macro_rules! value_ops {
+    ($($t:ty),+) => {
+        $(
+            value_ops!(@rhs $t, Expr<$t>, {});
+            value_ops!(@rhs $t, &'a Expr<$t>, {'a});
+            value_ops!(@rhs $t, &'a Property<$t>, {'a});
+            value_ops!(@rhs $t, ReadOnlyProperty<$t>, {});
+            value_ops!(@rhs $t, &'a ReadOnlyProperty<$t>, {'a});
+        )+
+    };
+    (@rhs $t:ty, $rhs:ty, {$($g:tt)*}) => {
+        impl<$($g)*> Add<$rhs> for $t {
+            type Output = Expr<$t>;
+            fn add(self, rhs: $rhs) -> Expr<$t> {
+                Expr(math::plus(self, rhs))
+            }
+        }
+
+        impl<$($g)*> Sub<$rhs> for $t {
+            type Output = Expr<$t>;
+            fn sub(self, rhs: $rhs) -> Expr<$t> {
+                Expr(math::minus(self, rhs))
+            }
+        }
+
+        impl<$($g)*> Mul<$rhs> for $t {
+            type Output = Expr<$t>;
+            fn mul(self, rhs: $rhs) -> Expr<$t> {
+                Expr(math::times(self, rhs))
+            }
+        }
+
+        impl<$($g)*> Div<$rhs> for $t {
+            type Output = Expr<$t>;
+            fn div(self, rhs: $rhs) -> Expr<$t> {
+                Expr(math::div(self, rhs))
+            }
+        }
+    };
+}
+
+value_ops!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
@@ -0,0 +1,52 @@
+use std::rc::Rc;
+
+use super::*;
+
+/// Apply `f` to an optional expression's value, producing `None` if it has none. `f` can return
+/// `None` as well.
+pub fn and_then<U, V, E, F>(value: E, f: F) -> Rc<Expression<Option<V>>>
+    where U: 'static, V: 'static + Clone, E: IntoExpression<Option<U>>, F: 'static + Fn(&U) -> Option<V> {
+    ::expr::unary(value, move |val| val.as_ref().and_then(&f))
+}
+
+pub fn is_none<U: 'static, E: IntoExpression<Option<U>>>(value: E) -> Rc<Expression<bool>> {
+    ::expr::unary(value, |val| val.is_none())
+}
+
+pub fn is_some<U: 'static, E: IntoExpression<Option<U>>>(value: E) -> Rc<Expression<bool>> {
+    ::expr::unary(value, |val| val.is_some())
+}
+
+/// Apply `f` to an optional expression's value, producing `None` if it has none.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct User { name: String }
+///
+/// let mut user: Property<Option<User>> = Property::new(None);
+/// let name = user.map_some(|user| user.name.clone()).unwrap_or(String::from("Guest"));
+/// assert_eq!("Guest", name.get());
+///
+/// user.set(Some(User { name: String::from("Jane") }));
+/// assert_eq!("Jane", name.get());
+/// ```
+pub fn map_some<U, V, E, F>(value: E, f: F) -> Rc<Expression<Option<V>>>
+    where U: 'static, V: 'static + Clone, E: IntoExpression<Option<U>>, F: 'static + Fn(&U) -> V {
+    ::expr::unary(value, move |val| val.as_ref().map(&f))
+}
+
+pub fn unwrap_or<U, E>(value: E, default: U) -> Rc<Expression<U>>
+    where U: 'static + Clone, E: IntoExpression<Option<U>> {
+    ::expr::unary(value, move |val| val.as_ref().unwrap_or(&default).clone())
+}
+
+/// Unwrap an optional expression's value, falling back to the value of another expression if it
+/// has none.
+pub fn unwrap_or_expr<U, E1, E2>(value: E1, other: E2) -> Rc<Expression<U>>
+    where U: 'static + Clone, E1: IntoExpression<Option<U>>, E2: IntoExpression<U> {
+    ::expr::binary(value, other, |val, other| val.as_ref().unwrap_or(other).clone())
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+use std::rc::Rc;
+
+use super::*;
+
+/// The message of a result expression's error, or `None` if it has no error. This pairs well with
+/// `text::parse`, for showing why some input was rejected.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut input = Property::new(String::from("12"));
+/// let error = input.parse::<u8>().err_message();
+/// assert_eq!(None, error.get());
+///
+/// input.set(String::from("1200"));
+/// assert_eq!(Some(String::from("number too large to fit in target type")), error.get());
+/// ```
+pub fn err_message<U, Err, E>(value: E) -> Rc<Expression<Option<String>>>
+    where U: 'static, Err: 'static + fmt::Display, E: IntoExpression<Result<U, Err>> {
+    ::expr::unary(value, |val| val.as_ref().err().map(|err| err.to_string()))
+}
+
+pub fn is_err<U: 'static, Err: 'static, E: IntoExpression<Result<U, Err>>>(value: E) -> Rc<Expression<bool>> {
+    ::expr::unary(value, |val| val.is_err())
+}
+
+pub fn is_ok<U: 'static, Err: 'static, E: IntoExpression<Result<U, Err>>>(value: E) -> Rc<Expression<bool>> {
+    ::expr::unary(value, |val| val.is_ok())
+}
+
+/// The value of a result expression, or `None` if it has an error.
+pub fn ok<U, Err, E>(value: E) -> Rc<Expression<Option<U>>>
+    where U: 'static + Clone, Err: 'static, E: IntoExpression<Result<U, Err>> {
+    ::expr::unary(value, |val| val.as_ref().ok().cloned())
+}
@@ -1,8 +1,108 @@
 use std::fmt;
 use std::rc::Rc;
+use std::str::FromStr;
 
 use super::*;
 
+/// Create a string expression from a format string and any number of source expressions, which
+/// is formatted again whenever any of them change. This works just like `format!`, including
+/// checking at compile time that the format string matches the arguments, except that each
+/// argument can be anything which converts into an expression of a `Display` type.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use]
+/// extern crate pebl;
+/// use pebl::prelude::*;
+///
+/// # fn main() {
+/// let name = Property::new(String::from("Jane"));
+/// let mut count = Property::new(3);
+/// let greeting = format_expr!("Hello, {}! You have {} messages", &name, &count);
+/// assert_eq!("Hello, Jane! You have 3 messages", greeting.get());
+///
+/// count.set(4);
+/// assert_eq!("Hello, Jane! You have 4 messages", greeting.get());
+/// # }
+/// ```
+///
+/// A format string which doesn't match its arguments is rejected by the compiler:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate pebl;
+/// use pebl::prelude::*;
+///
+/// # fn main() {
+/// let name = Property::new(String::from("Jane"));
+/// let greeting = format_expr!("Hello, {}! You have {} messages", &name);
+/// # }
+/// ```
+///
+/// # Limitations
+///
+/// Under the hood, every argument is converted into an expression of `Rc<Display>`, and the format
+/// string is handed to `format!` along with one placeholder per argument. As a result:
+///
+/// * Only `Display` formatting is supported. Fill, alignment, width and precision (such as
+///   `{:>8.2}`) are passed through, but other traits like `{:?}` or `{:x}` don't compile.
+/// * Arguments can be referred to by position (`{0}`), but not by name (`name = &value`).
+/// * Variables can't be captured inline (`{name}`), since they wouldn't be tracked. Captures
+///   are rejected by the compiler rather than formatted once and silently left behind:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate pebl;
+/// use pebl::prelude::*;
+///
+/// # fn main() {
+/// let name = String::from("Jane");
+/// let count = Property::new(3);
+/// let greeting = format_expr!("Hello, {name}! You have {} messages", &count);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! format_expr {
+    ($fmt:expr) => {
+        // Formatted in a 'static closure, so that inline captures are rejected here too
+        $crate::expr::unary($crate::expr::constant(()), |_| format!($fmt))
+    };
+    ($fmt:expr, $($arg:expr),+ $(,)*) => {{
+        let args = vec![$($crate::expr::text::display($arg)),+];
+        $crate::expr::unary($crate::expr::combine_vec(args), |args| {
+            let mut args = args.iter();
+            // Each argument is only named here to repeat once per source, in order
+            format!($fmt, $({ let _ = stringify!($arg); args.next().unwrap() }),+)
+        })
+    }};
+}
+
+/// The number of characters in a string. Unlike `len`, which counts bytes, this counts each
+/// character once, no matter how it is encoded.
+pub fn char_count<E: IntoExpression<String>>(value: E) -> Rc<Expression<usize>> {
+    ::expr::unary(value, |val| val.chars().count())
+}
+
+pub fn concat<E1: IntoExpression<String>, E2: IntoExpression<String>>(lhs: E1, rhs: E2) -> Rc<Expression<String>> {
+    ::expr::binary(lhs, rhs, |val1, val2| format!("{}{}", val1, val2))
+}
+
+pub fn contains<E1: IntoExpression<String>, E2: IntoExpression<String>>(value: E1, pattern: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(value, pattern, |val, pattern| val.contains(pattern.as_str()))
+}
+
+/// Wrap a value so it can be formatted later without knowing its type. This is used by
+/// `format_expr!`, and is rarely needed directly.
+#[doc(hidden)]
+pub fn display<T: 'static + Clone + fmt::Display, E: IntoExpression<T>>(value: E) -> Rc<Expression<Rc<fmt::Display>>> {
+    ::expr::unary(value, |val| Rc::new(val.clone()) as Rc<fmt::Display>)
+}
+
+pub fn ends_with<E1: IntoExpression<String>, E2: IntoExpression<String>>(value: E1, pattern: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(value, pattern, |val, pattern| val.ends_with(pattern.as_str()))
+}
+
 pub fn is_empty<E: IntoExpression<String>>(value: E) -> Rc<Expression<bool>> {
     ::expr::unary(value, |val| val.is_empty())
 }
@@ -11,10 +111,73 @@ pub fn len<E: IntoExpression<String>>(value: E) -> Rc<Expression<usize>> {
     ::expr::unary(value, |val| val.len())
 }
 
-pub fn to_string<T: 'static + fmt::Display + PartialEq, E: IntoExpression<T>>(value: E) -> Rc<Expression<String>> {
+/// Parse a string into some other type, such as a number. If the string can't be parsed, the
+/// expression's value is an `Err` describing why, which can be shown to the user directly.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut input = Property::new(String::from("42"));
+/// let age = expr::text::parse::<u32, _>(&input);
+/// assert_eq!(Ok(42), age.get());
+///
+/// input.set(String::from("forty-two"));
+/// assert_eq!(Err(String::from("invalid digit found in string")), age.get());
+/// ```
+pub fn parse<T, E: IntoExpression<String>>(value: E) -> Rc<Expression<Result<T, String>>>
+    where T: 'static + Clone + FromStr, T::Err: fmt::Display {
+    ::expr::unary(value, |val| val.parse::<T>().map_err(|err| err.to_string()))
+}
+
+pub fn replace<E1, E2, E3>(value: E1, from: E2, to: E3) -> Rc<Expression<String>>
+    where E1: IntoExpression<String>, E2: IntoExpression<String>, E3: IntoExpression<String> {
+    ::expr::map3(value, from, to, |val, from, to| val.replace(from.as_str(), to))
+}
+
+pub fn split<E1: IntoExpression<String>, E2: IntoExpression<String>>(value: E1, separator: E2) -> Rc<Expression<Vec<String>>> {
+    ::expr::binary(value, separator, |val, separator| val.split(separator.as_str()).map(String::from).collect())
+}
+
+pub fn starts_with<E1: IntoExpression<String>, E2: IntoExpression<String>>(value: E1, pattern: E2) -> Rc<Expression<bool>> {
+    ::expr::binary(value, pattern, |val, pattern| val.starts_with(pattern.as_str()))
+}
+
+/// The characters of a string in the range `start..end`, counted in characters rather than bytes.
+/// Any part of the range past the end of the string is ignored.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+///
+/// let mut name = Property::new(String::from("Zoë Smith"));
+/// let first_name = name.substring(0, 3);
+/// assert_eq!("Zoë", first_name.get());
+///
+/// name.set(String::from("Al"));
+/// assert_eq!("Al", first_name.get());
+/// ```
+pub fn substring<E1, E2, E3>(value: E1, start: E2, end: E3) -> Rc<Expression<String>>
+    where E1: IntoExpression<String>, E2: IntoExpression<usize>, E3: IntoExpression<usize> {
+    ::expr::map3(value, start, end, |val, &start, &end| {
+        val.chars().skip(start).take(end.saturating_sub(start)).collect()
+    })
+}
+
+pub fn to_lowercase<E: IntoExpression<String>>(value: E) -> Rc<Expression<String>> {
+    ::expr::unary(value, |val| val.to_lowercase())
+}
+
+pub fn to_string<T: 'static + fmt::Display, E: IntoExpression<T>>(value: E) -> Rc<Expression<String>> {
     ::expr::unary(value, |val| String::from(format!("{0}", val)))
 }
 
+pub fn to_uppercase<E: IntoExpression<String>>(value: E) -> Rc<Expression<String>> {
+    ::expr::unary(value, |val| val.to_uppercase())
+}
+
 pub fn trim<E: IntoExpression<String>>(value: E) -> Rc<Expression<String>> {
     ::expr::unary(value, |val| String::from(val.trim()))
 }
@@ -1,6 +1,11 @@
+pub mod computed;
+pub mod equality;
 pub mod expr;
 pub mod obsv;
 pub mod listen;
+pub mod notify;
 pub mod property;
 pub mod prelude;
+pub mod sync;
+pub mod validate;
 pub mod weak;
@@ -16,7 +16,7 @@ impl Listeners {
     }
     
     #[must_use]
-    pub fn listen_to<T: 'static + PartialEq, E: IntoExpression<T>>(&mut self, target: E) -> ListenChain {
+    pub fn listen_to<T: 'static, E: IntoExpression<T>>(&mut self, target: E) -> ListenChain {
         let expr = target.into_expr();
         let mut lc = ListenChain { owner: self, register_callbacks: Vec::with_capacity(1) };
         lc.register_callbacks.push(Box::new(move |handler| expr.add_invalidation_handler(handler)));
@@ -30,7 +30,7 @@ impl Listeners {
 
 impl<'a> ListenChain<'a> {
     #[must_use]
-    pub fn and<T: 'static + PartialEq, E: IntoExpression<T>>(self, target: E) -> ListenChain<'a> {
+    pub fn and<T: 'static, E: IntoExpression<T>>(self, target: E) -> ListenChain<'a> {
         let expr = target.into_expr();
         let mut lc = ListenChain { owner: self.owner, register_callbacks: self.register_callbacks };
         lc.register_callbacks.push(Box::new(move |handler| expr.add_invalidation_handler(handler)));
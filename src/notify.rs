@@ -0,0 +1,384 @@
+//! A module which controls *when* invalidation handlers get fired.
+//!
+//! By default, setting an `Observable<T>` fires its listeners right away. This is usually what you
+//! want, but if you are updating a bunch of related values in a row, listeners will run once per
+//! change and may see the data in a half-updated state. To avoid this, wrap your updates in a
+//! `batch` (or hold onto a `Transaction` guard). Any invalidation that happens while a batch is
+//! open is collected and each affected handler is fired exactly once when the outermost batch ends.
+//!
+//! Within a dispatch, handlers are fired in dependency order. Every handler has a rank, which is
+//! the height of the deepest expression it was registered through (an observable has a height of
+//! 0, and an expression is one higher than its tallest source). Handlers are fired lowest rank
+//! first, so by the time a handler listening to some expression fires, everything that expression
+//! depends on has already been brought up to date. Combined with the fact that each handler is only
+//! queued once, this means a listener fires exactly once per change, even if it depends on the
+//! changed value through several paths (such as `a.plus(a.times(b))`).
+//!
+//! Ranks are worked out when a handler is registered, and only ever go up. If an expression gets
+//! taller after that (for example, a `switch_map` switching to a deeper inner expression, or a
+//! `computed` closure reading a deeper chain than it did before), handlers registered through it
+//! keep their old rank. They are still fired exactly once per change, but may fire before some of
+//! their new dependencies have been brought up to date, so they should read values rather than
+//! assume they already have.
+//!
+//! Handlers are also allowed to set observables themselves. Rather than recursing, any
+//! invalidation triggered while handlers are being fired is queued up. Handlers ranked above the
+//! one currently firing are downstream of it, so they still fire in the current pass, while the
+//! rest are dispatched in a follow-up pass once the current one is done. If handlers keep
+//! triggering each other (for example, two observables that set each other to different values),
+//! dispatching gives up after `max_passes` passes and the remaining notifications are dropped,
+//! instead of hanging forever. Since this can also cut off a long but legitimate chain, the fact
+//! that it happened can be checked afterwards with `hit_pass_limit`.
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+use std::mem;
+use std::rc::{Rc, Weak};
+use std::thread;
+
+/// The number of passes `max_passes` allows by default.
+pub const DEFAULT_MAX_PASSES: usize = 100;
+
+thread_local! {
+    static STATE: RefCell<DispatchState> = RefCell::new(DispatchState::new());
+}
+
+/// An invalidation callback, along with the rank that decides when it fires relative to others.
+pub(crate) struct RankedCallback {
+    rank: Cell<usize>,
+    // Set while this callback is waiting in a dispatch queue, so it is never queued twice
+    queued: Cell<bool>,
+    // Set whenever this callback is queued, and only cleared by its owner once it has caught up.
+    // Unlike `queued`, this survives the queue being dropped without firing.
+    dirty: Cell<bool>,
+    callback: Box<Fn()>,
+}
+
+impl RankedCallback {
+    pub(crate) fn new<F: 'static + Fn()>(callback: F) -> RankedCallback {
+        RankedCallback { rank: Cell::new(0), queued: Cell::new(false), dirty: Cell::new(false), callback: Box::new(callback) }
+    }
+
+    /// Make sure this callback fires after anything feeding into an expression of the given
+    /// height. A callback registered through several expressions keeps the highest rank. Ranks
+    /// are never lowered, and are not updated if the expression grows later on.
+    pub(crate) fn raise_rank(&self, rank: usize) {
+        if rank > self.rank.get() {
+            self.rank.set(rank);
+        }
+    }
+
+    /// Returns `true` if this callback has been queued since it was last marked clean, whether or
+    /// not it has been fired since.
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    pub(crate) fn mark_clean(&self) {
+        self.dirty.set(false);
+    }
+}
+
+/// A callback waiting in a dispatch queue. Queues are max-heaps, so the ordering is reversed to pop
+/// the lowest rank first, with ties going to whichever callback was queued first.
+struct QueuedCallback {
+    rank: usize,
+    seq: usize,
+    callback: Weak<RankedCallback>,
+}
+
+impl QueuedCallback {
+    fn key(&self) -> (usize, usize) {
+        (self.rank, self.seq)
+    }
+}
+
+impl PartialEq for QueuedCallback {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for QueuedCallback {}
+
+impl PartialOrd for QueuedCallback {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedCallback {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key().cmp(&self.key())
+    }
+}
+
+/// Per-thread bookkeeping for open batches and in-progress dispatches.
+struct DispatchState {
+    depth: usize,
+    dispatching: bool,
+    max_passes: usize,
+    // Set if the last dispatch ran out of passes and dropped what was left
+    hit_pass_limit: bool,
+    // The rank of the handler currently firing, if any
+    rank: Option<usize>,
+    // Increases with every callback queued, to break ties between equal ranks
+    seq: usize,
+    pending: BinaryHeap<QueuedCallback>,
+    next_pass: BinaryHeap<QueuedCallback>,
+}
+
+impl DispatchState {
+    fn new() -> DispatchState {
+        DispatchState {
+            depth: 0,
+            dispatching: false,
+            max_passes: DEFAULT_MAX_PASSES,
+            hit_pass_limit: false,
+            rank: None,
+            seq: 0,
+            pending: BinaryHeap::new(),
+            next_pass: BinaryHeap::new(),
+        }
+    }
+
+    /// Queue a callback to be fired later, unless it is already queued. Callbacks that aren't
+    /// downstream of the one currently firing are put off until the next pass.
+    fn defer(&mut self, callback: &Rc<RankedCallback>) {
+        callback.dirty.set(true);
+        if callback.queued.replace(true) {
+            return;
+        }
+
+        let queued = QueuedCallback { rank: callback.rank.get(), seq: self.seq, callback: Rc::downgrade(callback) };
+        self.seq += 1;
+        match self.rank {
+            Some(current) if queued.rank <= current => self.next_pass.push(queued),
+            _ => self.pending.push(queued),
+        }
+    }
+
+    /// Remove and return the lowest ranked callback in the current pass, skipping any which were
+    /// dropped while queued.
+    fn pop_lowest(&mut self) -> Option<Rc<RankedCallback>> {
+        while let Some(queued) = self.pending.pop() {
+            if let Some(callback) = queued.callback.upgrade() {
+                callback.queued.set(false);
+                self.rank = Some(queued.rank);
+                return Some(callback);
+            }
+        }
+        None
+    }
+
+    /// Drop everything still queued, so those callbacks can be queued again later.
+    fn clear(&mut self) {
+        for queued in self.pending.drain().chain(self.next_pass.drain()) {
+            if let Some(callback) = queued.callback.upgrade() {
+                callback.queued.set(false);
+            }
+        }
+    }
+
+    /// Move on to the next pass, returning `false` if there is nothing left to fire.
+    fn start_next_pass(&mut self) -> bool {
+        self.rank = None;
+        mem::swap(&mut self.pending, &mut self.next_pass);
+        !self.pending.is_empty()
+    }
+}
+
+/// A guard which defers invalidation handlers until it is dropped.
+///
+/// Transactions can be nested freely - only when the outermost one ends do the collected handlers
+/// actually fire. If a transaction is exited early because of a panic, the collected handlers are
+/// dropped without being fired, since a handler panicking while the stack is already unwinding
+/// would abort the process. Derived expressions still pick up any values set before the panic
+/// the next time they are read.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use pebl::prelude::*;
+/// use pebl::notify::Transaction;
+///
+/// let mut p1 = Property::new(1);
+/// let mut p2 = Property::new(2);
+/// let count = Rc::new(Cell::new(0));
+/// let mut listeners = Listeners::new();
+/// {
+///     let count = count.clone();
+///     listeners.listen_to(&p1).and(&p2).with(move || count.set(count.get() + 1));
+/// }
+///
+/// let t = Transaction::begin();
+/// p1.set(10);
+/// p2.set(20);
+/// assert_eq!(0, count.get());
+/// t.commit();
+/// assert_eq!(1, count.get());
+/// ```
+pub struct Transaction {
+    // Batches are tracked per thread, so a transaction must never leave the thread that made it
+    _not_send: PhantomData<Rc<()>>,
+}
+
+impl Transaction {
+    /// Open a new transaction. Invalidation handlers will be deferred until it is dropped.
+    pub fn begin() -> Transaction {
+        STATE.with(|state| state.borrow_mut().depth += 1);
+        Transaction { _not_send: PhantomData }
+    }
+
+    /// End this transaction explicitly. This is the same as dropping it but reads better at the
+    /// call site.
+    pub fn commit(self) {}
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        let should_dispatch = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.depth -= 1;
+            if state.depth == 0 && !state.dispatching && thread::panicking() {
+                state.clear();
+                return false;
+            }
+            state.depth == 0 && !state.dispatching
+        });
+
+        if should_dispatch {
+            dispatch();
+        }
+    }
+}
+
+/// Run some closure as a single batch, deferring any invalidation handlers it triggers until it
+/// returns.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use pebl::prelude::*;
+///
+/// let mut first = Property::new(String::from("John"));
+/// let mut last = Property::new(String::from("Doe"));
+/// let count = Rc::new(Cell::new(0));
+/// let mut listeners = Listeners::new();
+/// {
+///     let count = count.clone();
+///     listeners.listen_to(&first).and(&last).with(move || count.set(count.get() + 1));
+/// }
+///
+/// batch(|| {
+///     first.set(String::from("Jane"));
+///     last.set(String::from("Roe"));
+/// });
+/// assert_eq!(1, count.get());
+/// ```
+pub fn batch<R, F: FnOnce() -> R>(f: F) -> R {
+    let _transaction = Transaction::begin();
+    f()
+}
+
+/// Returns `true` if a batch is currently open on this thread.
+pub fn is_batching() -> bool {
+    STATE.with(|state| state.borrow().depth > 0)
+}
+
+/// Set the maximum number of passes a single dispatch may run before giving up. Each pass fires
+/// the handlers invalidated by the previous one, so this effectively limits how long a chain of
+/// handlers setting other observables can get. This setting is per thread.
+pub fn set_max_passes(max_passes: usize) {
+    STATE.with(|state| state.borrow_mut().max_passes = max_passes);
+}
+
+/// Return the maximum number of passes a single dispatch may run. See `set_max_passes`.
+pub fn max_passes() -> usize {
+    STATE.with(|state| state.borrow().max_passes)
+}
+
+/// Returns `true` if the most recent dispatch on this thread gave up after `max_passes` passes. When
+/// that happens, any notifications which were still queued are dropped, so some listeners may not
+/// have seen the latest values. This is reset at the start of every dispatch.
+///
+/// # Example
+///
+/// ```
+/// use pebl::notify;
+/// use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+///
+/// let mut o = Observable::new(0);
+/// let o_ptr = ObservablePtr::new(&o);
+/// // A handler which keeps changing the value it listens to never settles
+/// let handler = InvalidationHandler::new(move || {
+///     let value = *o_ptr.deref().get();
+///     o_ptr.clone().deref_mut().set(value + 1);
+/// });
+/// o.add_invalidation_handler(&handler);
+///
+/// o.set(1);
+/// assert!(notify::hit_pass_limit());
+/// ```
+pub fn hit_pass_limit() -> bool {
+    STATE.with(|state| state.borrow().hit_pass_limit)
+}
+
+/// Fire a list of callbacks, or queue them up if a batch is currently open or if we are already
+/// in the middle of firing callbacks.
+pub(crate) fn fire(callbacks: Vec<Rc<RankedCallback>>) {
+    let should_dispatch = STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        for callback in &callbacks {
+            state.defer(callback);
+        }
+        state.depth == 0 && !state.dispatching
+    });
+
+    if should_dispatch {
+        dispatch();
+    }
+}
+
+/// Resets the dispatching flag once a dispatch ends, even if a handler panics.
+struct DispatchGuard;
+
+impl Drop for DispatchGuard {
+    fn drop(&mut self) {
+        STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            state.dispatching = false;
+            state.rank = None;
+            state.clear();
+        });
+    }
+}
+
+/// Fire all pending callbacks, pass after pass, until none are left or we run out of passes.
+fn dispatch() {
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.dispatching = true;
+        state.hit_pass_limit = false;
+    });
+    let _guard = DispatchGuard;
+
+    for _ in 0..max_passes() {
+        while let Some(callback) = STATE.with(|state| state.borrow_mut().pop_lowest()) {
+            (callback.callback)();
+        }
+
+        if !STATE.with(|state| state.borrow_mut().start_next_pass()) {
+            return;
+        }
+    }
+
+    // Still more to fire, which the guard is about to drop
+    STATE.with(|state| state.borrow_mut().hit_pass_limit = true);
+}
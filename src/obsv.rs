@@ -9,72 +9,281 @@
 //! This is similar to an `UnsafeCell<T>` with an additional set of `try_deref` and `try_deref_mut`
 //! methods which can be used to safely deref the pointer.
 //!
+//! An observable's data lives on the heap, shared between the observable and any pointers to it,
+//! so it is always safe to move an observable around while pointers to it exist.
+//!
 //! This class acts as the core of the `Property<T>` struct, and the fact it supports a pointer
 //! concept makes it easier to create bindings to targets which may get deallocated at any time.
 
-use std::cell::UnsafeCell;
+use std::cell::{RefCell, UnsafeCell};
 use std::ops::{Deref, DerefMut};
 use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
 use std::rc::{Rc, Weak};
+use equality::{EqualityPolicy, PartialEqPolicy};
+use expr::Expression;
+use computed;
+use notify::{self, RankedCallback, Transaction};
 use weak::WeakList;
 
 /// A callback which gets fired when a target `Observable<T>` changes.
 pub struct InvalidationHandler {
-    callback: Rc<Fn()>,
+    callback: Rc<RankedCallback>,
 }
 
 impl InvalidationHandler {
     pub fn new<F: 'static + Fn()>(callback: F) -> Self {
-        InvalidationHandler { callback: Rc::new(callback) }
+        InvalidationHandler { callback: Rc::new(RankedCallback::new(callback)) }
+    }
+
+    /// Called by expressions as this handler is registered through them, so that it fires only
+    /// after every source of the expression is up to date. See the `notify` module for details.
+    pub(crate) fn raise_rank(&self, height: usize) {
+        self.callback.raise_rank(height);
+    }
+
+    /// Returns `true` if one of this handler's sources has changed since `mark_clean` was last
+    /// called. This is set as soon as the handler is queued, so it stays accurate while the
+    /// handler is deferred by a batch, and even if the handler is dropped from the queue without
+    /// being fired (after a panic, or when a dispatch runs out of passes).
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.callback.is_dirty()
+    }
+
+    pub(crate) fn mark_clean(&self) {
+        self.callback.mark_clean();
+    }
+
+    pub(crate) fn callback(&self) -> &Rc<RankedCallback> {
+        &self.callback
+    }
+}
+
+/// A callback which gets fired when a target `Observable<T>` is dropped.
+///
+/// This is kept separate from `InvalidationHandler` so that listeners can tell a real change in
+/// value apart from their source going away.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use pebl::obsv::{DisposalHandler, Observable};
+///
+/// let val = Observable::new(42);
+/// let disposed = Rc::new(Cell::new(false));
+/// let disposed_clone = disposed.clone();
+/// let handler = DisposalHandler::new(move || disposed_clone.set(true));
+/// val.add_disposal_handler(&handler);
+///
+/// assert!(!disposed.get());
+/// drop(val);
+/// assert!(disposed.get());
+/// ```
+pub struct DisposalHandler {
+    callback: Rc<Fn()>,
+}
+
+impl DisposalHandler {
+    pub fn new<F: 'static + Fn()>(callback: F) -> Self {
+        DisposalHandler { callback: Rc::new(callback) }
+    }
+
+    /// Fire this handler directly, for targets which were already disposed before it could be
+    /// registered with them.
+    pub(crate) fn fire(&self) {
+        (self.callback)();
+    }
+
+    pub(crate) fn callback(&self) -> &Rc<Fn()> {
+        &self.callback
+    }
+}
+
+/// A callback which gets fired with both the old and the new value when a target changes.
+///
+/// Unlike an `InvalidationHandler`, which only learns that *something* changed, a change handler is
+/// useful when you need to know what the value used to be, such as for diffing or animating.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+/// use pebl::obsv::{ChangeHandler, Observable};
+///
+/// let mut val = Observable::new(1);
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// let log_clone = log.clone();
+/// let handler = ChangeHandler::new(move |old: &i32, new: &i32| log_clone.borrow_mut().push((*old, *new)));
+/// val.add_change_handler(&handler);
+///
+/// val.set(2);
+/// val.set(2); // No change, so no callback
+/// val.set(3);
+/// assert_eq!(vec![(1, 2), (2, 3)], *log.borrow());
+/// ```
+pub struct ChangeHandler<T> {
+    callback: Rc<Fn(&T, &T)>,
+    // Expressions don't store their values, so we keep track of them on their behalf, using
+    // invalidation handlers which live as long as this change handler does
+    watchers: RefCell<Vec<InvalidationHandler>>,
+}
+
+impl<T> ChangeHandler<T> {
+    pub fn new<F: 'static + Fn(&T, &T)>(callback: F) -> Self {
+        ChangeHandler { callback: Rc::new(callback), watchers: RefCell::new(Vec::new()) }
+    }
+}
+
+impl<T: 'static + PartialEq + Clone> ChangeHandler<T> {
+    /// Listen to some target expression, remembering its last value so it can be reported to our
+    /// callback when the expression is invalidated.
+    pub(crate) fn watch(&self, expr: Rc<Expression<T>>) {
+        let callback = Rc::downgrade(&self.callback);
+        let last_value = RefCell::new(expr.try_get());
+        let expr_clone = expr.clone();
+        let watcher = InvalidationHandler::new(move || {
+            let new_value = expr_clone.try_get();
+            if let (Some(callback), Some(old), Some(new)) = (callback.upgrade(), last_value.borrow().as_ref(), new_value.as_ref()) {
+                if old != new {
+                    callback(old, new);
+                }
+            }
+            *last_value.borrow_mut() = new_value;
+        });
+        expr.add_invalidation_handler(&watcher);
+        self.watchers.borrow_mut().push(watcher);
     }
 }
 
 /// Core data which is wrapped by `Observable<T>`
-struct ObservableData<T: PartialEq> {
+struct ObservableData<T> {
     value: T,
-    handle: Rc<()>,
+    policy: Box<EqualityPolicy<T>>,
+    // Set when the owning `Observable<T>` is dropped. The data itself may outlive it briefly, if
+    // a pointer to it is being dereferenced at the time.
+    disposed: bool,
     borrow_counts: BorrowCounts,
-    on_invalidated: WeakList<Fn()>,
+    on_invalidated: InvalidationList,
+    on_disposed: DisposalList,
+    on_changed: WeakList<Fn(&T, &T)>,
+    // Set once a change handler is registered (which requires `T: Clone`), so that `modify_inner`
+    // can remember the old value
+    snapshot: Option<fn(&T) -> T>,
+    // Old and new values waiting to be reported to change handlers, once nothing is mutably
+    // borrowing this data anymore
+    pending_changes: Vec<(T, T)>,
 }
 
-impl<T: PartialEq> ObservableData<T> {
+impl<T> ObservableData<T> {
     fn get(&self) -> &T {
         &self.value
     }
 
+    /// Read this observable's value on behalf of a user, letting any computed expression currently
+    /// being evaluated know that it depends on us.
+    fn read(&self) -> &T {
+        computed::record_read(&self.on_invalidated, &self.on_disposed, 0);
+        &self.value
+    }
+
     fn get_mut(&mut self) -> &mut T {
         &mut self.value
     }
 
     /// Set this observable's value, triggering invalidation callbacks if this new value is not
-    /// equal to the current value.
+    /// equal to the current value, according to our equality policy.
     fn set(&mut self, value: T) {
-        if self.value != value {
-            self.value = value;
+        if !self.policy.equals(&self.value, &value) {
+            let old = mem::replace(&mut self.value, value);
+            self.queue_changed(old);
+            self.fire_changed();
             self.fire_invalidated();
         }
     }
 
-    /// Trigger the invalidation handlers of any listeners.
+    /// Set this observable's value like `set`, but without triggering invalidation callbacks. This
+    /// is for values that are pulled in lazily, after their invalidation was already announced.
+    fn refresh(&mut self, value: T) {
+        if !self.policy.equals(&self.value, &value) {
+            let old = mem::replace(&mut self.value, value);
+            self.queue_changed(old);
+            self.fire_changed();
+        }
+    }
+
+    /// Remember a change to report to change handlers, if there are any. A snapshot of the new
+    /// value is taken, so that the value is free to change again before handlers get to see it.
+    fn queue_changed(&mut self, old: T) {
+        if let Some(snapshot) = self.snapshot {
+            if self.on_changed.len() > 0 {
+                let new = snapshot(&self.value);
+                self.pending_changes.push((old, new));
+            }
+        }
+    }
+
+    /// Trigger the change handlers of any listeners with every change queued so far. Unlike
+    /// invalidation handlers, these are never deferred by a batch, as they describe each individual
+    /// change. They are only held back while the value is mutably borrowed, and then fired as soon
+    /// as the borrow is released, so that handlers can read the value themselves.
+    fn fire_changed(&mut self) {
+        if self.borrow_counts.mutable {
+            return;
+        }
+        while !self.pending_changes.is_empty() {
+            for (old, new) in mem::take(&mut self.pending_changes) {
+                for callback in self.on_changed.upgrade() {
+                    callback(&old, &new);
+                }
+            }
+        }
+    }
+
+    fn add_change_handler(&mut self, handler: &ChangeHandler<T>) where T: Clone {
+        self.snapshot = Some(T::clone);
+        self.on_changed.push(&handler.callback);
+    }
+
+    /// Trigger the invalidation handlers of any listeners. If a batch is open, this is deferred
+    /// until it ends.
     fn fire_invalidated(&self) {
-        for callback in self.on_invalidated.upgrade() {
+        let callbacks = self.on_invalidated.borrow().upgrade();
+        notify::fire(callbacks);
+    }
+
+    /// Mark this data as no longer reachable through pointers, triggering disposal handlers.
+    fn dispose(&mut self) {
+        // Disposal handlers are never deferred - by the time a batch ended, there would be no
+        // source left for them to look at
+        let callbacks = self.on_disposed.borrow().upgrade();
+        for callback in callbacks {
             callback();
         }
+        self.disposed = true;
     }
 }
 
-impl<T: PartialEq> Drop for ObservableData<T> {
-    fn drop(&mut self) {
-        self.fire_invalidated();
-    }
-}
+/// The invalidation handlers registered with an observable. This is shared, so that computed
+/// expressions can subscribe to an observable they read without knowing its type.
+pub(crate) type InvalidationList = Rc<RefCell<WeakList<RankedCallback>>>;
+
+/// The disposal handlers registered with an observable, shared for the same reason as its
+/// invalidation handlers.
+pub(crate) type DisposalList = Rc<RefCell<WeakList<Fn()>>>;
+
+/// Heap storage for `ObservableData<T>`, which stays at a stable address as its owner moves.
+type ObservableCell<T> = Rc<UnsafeCell<ObservableData<T>>>;
 
 /// A data value which can have listeners attached which are triggered when it changes
-pub struct Observable<T: PartialEq> {
-    cell: UnsafeCell<ObservableData<T>>,
+pub struct Observable<T> {
+    cell: ObservableCell<T>,
 }
 
-impl<T: PartialEq> Observable<T> {
+impl<T> Observable<T> {
     /// Create a new observable
     ///
     /// # Example
@@ -96,19 +305,50 @@ impl<T: PartialEq> Observable<T> {
     /// assert_eq!(9001, *val.get());
     /// assert_eq!(1, *times_modified.borrow());
     /// ```
-    pub fn new(value: T) -> Self {
+    pub fn new(value: T) -> Self where T: PartialEq {
+        Observable::with_policy(value, PartialEqPolicy)
+    }
+
+    /// Create a new observable which uses a custom policy to decide whether a new value is
+    /// different from the current one. See the `equality` module for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use pebl::equality::Approx;
+    /// use pebl::obsv::{Observable, InvalidationHandler};
+    ///
+    /// let mut val = Observable::with_policy(1.0, Approx(0.1));
+    /// let count = Rc::new(Cell::new(0));
+    /// let count_clone = count.clone();
+    /// let handler = InvalidationHandler::new(move || count_clone.set(count_clone.get() + 1));
+    /// val.add_invalidation_handler(&handler);
+    ///
+    /// val.set(1.01);
+    /// assert_eq!(0, count.get());
+    /// val.set(2.0);
+    /// assert_eq!(1, count.get());
+    /// ```
+    pub fn with_policy<P: 'static + EqualityPolicy<T>>(value: T, policy: P) -> Self {
         let data = ObservableData {
             value: value,
-            handle: Rc::new(()),
+            policy: Box::new(policy),
+            disposed: false,
             borrow_counts: BorrowCounts::new(),
-            on_invalidated: WeakList::with_capacity(1),
+            on_invalidated: Rc::new(RefCell::new(WeakList::with_capacity(1))),
+            on_disposed: Rc::new(RefCell::new(WeakList::new())),
+            on_changed: WeakList::new(),
+            snapshot: None,
+            pending_changes: Vec::new(),
         };
-        Observable { cell: UnsafeCell::new(data) }
+        Observable { cell: Rc::new(UnsafeCell::new(data)) }
     }
 
     /// Get a reference to the value contained by this observable.
     pub fn get(&self) -> &T {
-        &self.get_data().get()
+        self.get_data().read()
     }
 
     /// Set the value of this observable. If the value changes, it will trigger any listeners that
@@ -133,16 +373,43 @@ impl<T: PartialEq> Observable<T> {
     /// ```
     #[must_use]
     pub fn modify_inner(&mut self) -> ModifyInnerRef<T> {
-        ModifyInnerRef::new(self.get_data())
+        ModifyInnerRef::new(self.get_data(), true)
     }
 
     /// Register a listener which will be triggered whenever this observable's value is updated.
     pub fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
-        self.get_data().on_invalidated.push(&handler.callback);
+        self.get_data().on_invalidated.borrow_mut().push(&handler.callback);
+    }
+
+    /// Let any computed expression currently being evaluated know that it depends on this
+    /// observable, which stands in for an expression of the given height. The disposal of that
+    /// expression's sources is reported through `on_disposed`.
+    pub(crate) fn record_read(&self, on_disposed: &DisposalList, height: usize) {
+        computed::record_read(&self.get_data().on_invalidated, on_disposed, height);
+    }
+
+    /// Register a listener which will be triggered when this observable is dropped. Invalidation
+    /// handlers are *not* triggered in that case.
+    pub fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        self.get_data().on_disposed.borrow_mut().push(&handler.callback);
+    }
+
+    /// Register a listener which will be triggered with the old and new values whenever this
+    /// observable's value changes. When the value is updated through `modify_inner`, the old value
+    /// is a copy taken before the modification started.
+    pub fn add_change_handler(&self, handler: &ChangeHandler<T>) where T: Clone {
+        self.get_data().add_change_handler(handler);
     }
 
     fn get_data(&self) -> &mut ObservableData<T> {
-        unsafe { &mut (*self.cell.get()) }
+        data_of(&self.cell)
+    }
+}
+
+impl<T> Drop for Observable<T> {
+    fn drop(&mut self) {
+        // Sources are still readable while disposal handlers run, as our data hasn't been dropped
+        self.get_data().dispose();
     }
 }
 
@@ -152,7 +419,7 @@ impl<T: Default + PartialEq> Default for Observable<T> {
     }
 }
 
-impl<T: Default + PartialEq> Observable<T> {
+impl<T: Default> Observable<T> {
     /// If this observable supports the `Default` trait, call this convenience method to reset it to
     /// its default value.
     ///
@@ -175,73 +442,94 @@ impl<T: Default + PartialEq> Observable<T> {
 }
 
 /// Intermediate struct created to handle immutable borrows from an `ObservablePtr`.
-pub struct ObservableRef<'a, T: 'a + PartialEq> {
-    obsv_ptr: &'a ObservablePtr<T>,
+pub struct ObservableRef<'a, T: 'a> {
+    // Holding a strong reference keeps the data alive even if its owner is dropped mid-borrow
+    cell: ObservableCell<T>,
+    _ptr: PhantomData<&'a ObservablePtr<T>>,
 }
 
-impl<'a, T: 'a + PartialEq> ObservableRef<'a, T> {
-    // Only call this if you know that `data_ptr` can safely be dereferenced
-    fn new(obsv_ptr: &'a ObservablePtr<T>) -> Self {
-        // Safe to call during lifetime of ObservableRef
-        unsafe { obsv_ptr.deref_data().borrow_counts.count_borrow(); } // Uncounted on Drop
-        ObservableRef { obsv_ptr: obsv_ptr }
+impl<'a, T: 'a> ObservableRef<'a, T> {
+    fn new(cell: ObservableCell<T>) -> Self {
+        data_of(&cell).borrow_counts.count_borrow(); // Uncounted on Drop
+        ObservableRef { cell: cell, _ptr: PhantomData }
     }
 
     pub fn get(&self) -> &T {
-        // Safe to call during lifetime of ObservableRef
-        unsafe { self.obsv_ptr.get() }
+        data_of(&self.cell).read()
     }
 
     pub fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
-        // Safe to call during lifetime of ObservableRef
-        unsafe { self.obsv_ptr.add_invalidation_handler(handler); }
+        data_of(&self.cell).on_invalidated.borrow_mut().push(&handler.callback);
+    }
+
+    pub fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        data_of(&self.cell).on_disposed.borrow_mut().push(&handler.callback);
+    }
+
+    pub fn add_change_handler(&self, handler: &ChangeHandler<T>) where T: Clone {
+        data_of(&self.cell).add_change_handler(handler);
+    }
+
+    /// Trigger invalidation handlers without changing the value, to announce that it is out of date
+    /// and will be refreshed when next read.
+    pub(crate) fn invalidate(&self) {
+        data_of(&self.cell).fire_invalidated();
     }
 }
 
-impl<'a, T: 'a + PartialEq> Drop for ObservableRef<'a, T> {
+impl<'a, T: 'a> Drop for ObservableRef<'a, T> {
     fn drop(&mut self) {
-        // Safe to call during lifetime of ObservableRef
-        unsafe { self.obsv_ptr.deref_data().borrow_counts.count_unborrow(); }
+        data_of(&self.cell).borrow_counts.count_unborrow();
     }
 }
 
 /// Intermediate struct created to handle mutable borrows from an `ObservablePtr`.
-pub struct ObservableMutRef<'a, T: 'a + PartialEq> {
-    obsv_ptr: &'a mut ObservablePtr<T>,
+///
+/// Any listeners triggered through this reference are deferred until it is dropped, so that they
+/// are free to read the value again.
+pub struct ObservableMutRef<'a, T: 'a> {
+    // Holding a strong reference keeps the data alive even if its owner is dropped mid-borrow
+    cell: ObservableCell<T>,
+    _ptr: PhantomData<&'a mut ObservablePtr<T>>,
+    // Fields are dropped after `drop` releases the borrow, so listeners fire once it's released
+    _transaction: Transaction,
 }
 
-impl<'a, T: 'a + PartialEq> ObservableMutRef<'a, T> {
-    // Only call this if you know that `data_ptr` can safely be dereferenced
-    fn new(obsv_ptr: &'a mut ObservablePtr<T>) -> Self {
-        // Safe to call during lifetime of ObservableRef
-        unsafe { obsv_ptr.deref_data().borrow_counts.count_borrow_mut(); } // Uncounted on Drop
-        ObservableMutRef { obsv_ptr: obsv_ptr }
+impl<'a, T: 'a> ObservableMutRef<'a, T> {
+    fn new(cell: ObservableCell<T>) -> Self {
+        data_of(&cell).borrow_counts.count_borrow_mut(); // Uncounted on Drop
+        ObservableMutRef { cell: cell, _ptr: PhantomData, _transaction: Transaction::begin() }
     }
 
     pub fn get(&self) -> &T {
-        // Safe to call during lifetime of ObservableMutRef
-        unsafe { self.obsv_ptr.get() }
+        data_of(&self.cell).get()
     }
 
     pub fn set(&mut self, value: T) {
-        // Safe to call during lifetime of ObservableMutRef
-        unsafe { self.obsv_ptr.set(value); }
+        data_of(&self.cell).set(value);
+    }
+
+    /// Set a value whose invalidation was already announced with `ObservableRef::invalidate`.
+    /// Change handlers still fire, but invalidation handlers don't fire a second time.
+    pub(crate) fn refresh(&mut self, value: T) {
+        data_of(&self.cell).refresh(value);
     }
 
     pub fn modify_inner(&mut self) -> ModifyInnerRef<T> {
-        unsafe { self.obsv_ptr.modify_inner() }
+        // We already hold the mutable borrow, and lend it out for as long as the result lives
+        ModifyInnerRef::new(data_of(&self.cell), false)
     }
 
     pub fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
-        // Safe to call during lifetime of ObservableMutRef
-        unsafe { self.obsv_ptr.add_invalidation_handler(handler); }
+        data_of(&self.cell).on_invalidated.borrow_mut().push(&handler.callback);
     }
 }
 
-impl<'a, T: 'a + PartialEq> Drop for ObservableMutRef<'a, T> {
+impl<'a, T: 'a> Drop for ObservableMutRef<'a, T> {
     fn drop(&mut self) {
-        // Safe to call during lifetime of ObservableMutRef
-        unsafe { self.obsv_ptr.deref_data().borrow_counts.count_unborrow_mut(); }
+        let data = data_of(&self.cell);
+        data.borrow_counts.count_unborrow_mut();
+        data.fire_changed();
     }
 }
 
@@ -250,33 +538,51 @@ impl<'a, T: 'a + PartialEq> Drop for ObservableMutRef<'a, T> {
 /// very large data value, such as a long `String` or large `Vec`. When this struct is dropped, it
 /// automatically fires any listeners registered with this observable, ensuring that any change or
 /// changes made will be picked up.
-pub struct ModifyInnerRef<'a, T: 'a + PartialEq> {
+pub struct ModifyInnerRef<'a, T: 'a> {
     data: &'a mut ObservableData<T>,
+    old_value: Option<T>,
+    // Whether this counted a mutable borrow of its own, rather than borrowing from an
+    // `ObservableMutRef` which already holds one
+    counted: bool,
 }
 
-impl<'a, T: 'a + PartialEq> ModifyInnerRef<'a, T> {
-    fn new(data: &'a mut ObservableData<T>) -> Self {
-        data.borrow_counts.count_borrow_mut(); // Uncounted on drop
-        ModifyInnerRef { data: data }
+impl<'a, T: 'a> ModifyInnerRef<'a, T> {
+    fn new(data: &'a mut ObservableData<T>, counted: bool) -> Self {
+        if counted {
+            data.borrow_counts.count_borrow_mut(); // Uncounted on drop
+        }
+        let old_value = match data.snapshot {
+            Some(snapshot) if data.on_changed.len() > 0 => Some(snapshot(&data.value)),
+            _ => None,
+        };
+        ModifyInnerRef { data: data, old_value: old_value, counted: counted }
     }
 }
 
-impl<'a, T: 'a + PartialEq> Deref for ModifyInnerRef<'a, T> {
+impl<'a, T: 'a> Deref for ModifyInnerRef<'a, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
         self.data.get()
     }
 }
 
-impl<'a, T: 'a + PartialEq> DerefMut for ModifyInnerRef<'a, T> {
+impl<'a, T: 'a> DerefMut for ModifyInnerRef<'a, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.data.get_mut()
     }
 }
 
-impl<'a, T: 'a + PartialEq> Drop for ModifyInnerRef<'a, T> {
+impl<'a, T: 'a> Drop for ModifyInnerRef<'a, T> {
     fn drop(&mut self) {
-        self.data.borrow_counts.count_unborrow_mut();
+        if self.counted {
+            self.data.borrow_counts.count_unborrow_mut();
+        }
+        if let Some(old_value) = self.old_value.take() {
+            if !self.data.policy.equals(&old_value, &self.data.value) {
+                self.data.queue_changed(old_value);
+                self.data.fire_changed();
+            }
+        }
         self.data.fire_invalidated();
     }
 }
@@ -311,36 +617,26 @@ impl<'a, T: 'a + PartialEq> Drop for ModifyInnerRef<'a, T> {
 /// op.deref_mut().set(999); // Legal but dangerous if you're not careful
 /// assert_eq!(999, *o_imm.get());
 /// ```
-pub struct ObservablePtr<T: PartialEq> {
-    cell_ptr: *const UnsafeCell<ObservableData<T>>,
-    valid_handle: Weak<()>,
+pub struct ObservablePtr<T> {
+    cell: Weak<UnsafeCell<ObservableData<T>>>,
 }
 
-impl<T: PartialEq> ObservablePtr<T> {
+impl<T> ObservablePtr<T> {
     /// Create a pointer to a target `Observable<T>`
     pub fn new(target: &Observable<T>) -> ObservablePtr<T> {
-        ObservablePtr::<T> {
-            cell_ptr: &target.cell,
-            valid_handle: Rc::downgrade(&target.get_data().handle),
-        }
+        ObservablePtr::<T> { cell: Rc::downgrade(&target.cell) }
     }
 
     /// Attempt to dereference this pointer. If the target observable has been dropped, this
     /// optional will return `None`.
     pub fn try_deref<'a>(&'a self) -> Option<ObservableRef<'a, T>> {
-        if self.can_deref() {
-            return Some(ObservableRef::new(self));
-        }
-        None
+        self.upgrade().map(ObservableRef::new)
     }
 
     /// Attempt to dereference this pointer mutably. If the target observable has been dropped, this
     /// optional will return `None`.
     pub fn try_deref_mut<'a>(&'a mut self) -> Option<ObservableMutRef<'a, T>> {
-        if self.can_deref() {
-            return Some(ObservableMutRef::new(self));
-        }
-        None
+        self.upgrade().map(ObservableMutRef::new)
     }
 
     /// Convenience method for unwrapping the optional returned by `try_deref`, when you are sure
@@ -355,63 +651,39 @@ impl<T: PartialEq> ObservablePtr<T> {
         self.try_deref_mut().unwrap()
     }
 
-    fn can_deref(&self) -> bool {
-        self.valid_handle.upgrade().is_some()
-    }
-
-    // Undefined behavior if `can_deref` is not true
-    unsafe fn deref_data(&self) -> &mut ObservableData<T> {
-        &mut *((*self.cell_ptr).get())
-    }
-
-    // Undefined behavior if `can_deref` is not true
-    unsafe fn get(&self) -> &T {
-        &self.deref_data().get()
-    }
-
-    // Undefined behavior if `can_deref` is not true
-    unsafe fn set(&mut self, value: T) {
-        self.deref_data().set(value);
-    }
-    // Undefined behavior if `can_deref` is not true
-    unsafe fn modify_inner(&mut self) -> ModifyInnerRef<T> {
-        ModifyInnerRef::new(self.deref_data())
-    }
-
-    // Undefined behavior if `can_deref` is not true
-    unsafe fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
-        self.deref_data().on_invalidated.push(&handler.callback);
+    fn upgrade(&self) -> Option<ObservableCell<T>> {
+        match self.cell.upgrade() {
+            Some(ref cell) if data_of(cell).disposed => None,
+            cell => cell,
+        }
     }
 }
 
-impl<T: PartialEq> Clone for ObservablePtr<T> {
+impl<T> Clone for ObservablePtr<T> {
     fn clone(&self) -> Self {
-        ObservablePtr {
-            cell_ptr: self.cell_ptr,
-            valid_handle: self.valid_handle.clone(),
-        }
+        ObservablePtr { cell: self.cell.clone() }
     }
 }
 
-impl<T: fmt::Debug + PartialEq> fmt::Debug for Observable<T> {
+impl<T: fmt::Debug> fmt::Debug for Observable<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Observable {{ {:?} }}", self.get())
     }
 }
 
-impl<'a, T: fmt::Debug + PartialEq> fmt::Debug for ObservableRef<'a, T> {
+impl<'a, T: fmt::Debug> fmt::Debug for ObservableRef<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "&Observable {{ {:?} }}", self.get())
     }
 }
 
-impl<'a, T: fmt::Debug + PartialEq> fmt::Debug for ObservableMutRef<'a, T> {
+impl<'a, T: fmt::Debug> fmt::Debug for ObservableMutRef<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "&mut Observable {{ {:?} }}", self.get())
     }
 }
 
-impl<T: fmt::Debug + PartialEq> fmt::Debug for ObservablePtr<T> {
+impl<T: fmt::Debug> fmt::Debug for ObservablePtr<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         return match self.try_deref() {
             None => write!(f, "*Observable {{ null }}"),
@@ -420,6 +692,12 @@ impl<T: fmt::Debug + PartialEq> fmt::Debug for ObservablePtr<T> {
     }
 }
 
+/// Access the data inside an observable's heap storage. Like the `UnsafeCell` it wraps, this hands
+/// out mutable access from a shared reference; `BorrowCounts` is what keeps callers honest.
+fn data_of<T>(cell: &ObservableCell<T>) -> &mut ObservableData<T> {
+    unsafe { &mut (*cell.get()) }
+}
+
 /// A struct for detailing how many current borrows are being made on an intermediate reference.
 /// This is useful to provide runtime panic behavior similar to Rust's normal compile-time
 /// restrictions.
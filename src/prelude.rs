@@ -1,6 +1,9 @@
+pub use computed::{autorun, computed, Autorun};
 pub use expr;
 pub use expr::Expression;
 pub use expr::CoreExpressions;
-pub use obsv::InvalidationHandler;
+pub use expr::Expr;
+pub use obsv::{ChangeHandler, DisposalHandler, InvalidationHandler};
 pub use listen::Listeners;
-pub use property::Property;
\ No newline at end of file
+pub use notify::batch;
+pub use property::{Property, ReadOnlyProperty};
\ No newline at end of file
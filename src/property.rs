@@ -1,18 +1,125 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt;
-use std::rc::Rc;
-use obsv::{InvalidationHandler, ModifyInnerRef, Observable, ObservablePtr};
-use expr::{CoreExpressions, Expression, IntoExpression};
+use std::rc::{Rc, Weak};
+use equality::{EqualityPolicy, PartialEqPolicy};
+use obsv::{ChangeHandler, DisposalHandler, InvalidationHandler, ModifyInnerRef, Observable, ObservablePtr};
+use expr::{self, CoreExpressions, Expression, IntoExpression};
+use validate::ValidationError;
 
 /// Data for linking to some target `Expression<T>`. When the expression's value changes, this
 /// struct's `dirty` value will be set to `true`.
-struct Binding<T: PartialEq> {
+///
+/// If a source of the expression is dropped, the binding is `disposed` and stops tracking it,
+/// holding onto the last value the expression produced.
+struct Binding<T> {
     expr: Rc<Expression<T>>,
     #[allow(dead_code)] // Needed to keep weak ref alive
     handle: InvalidationHandler,
+    #[allow(dead_code)] // Needed to keep weak ref alive
+    disposal_handle: DisposalHandler,
     dirty: Rc<Cell<bool>>,
+    disposed: Rc<Cell<bool>>,
+    final_value: Rc<RefCell<Option<T>>>,
+}
+
+/// A property's validators, along with an observable reporting the last error they produced. This
+/// is shared with the other side of a two-way binding, so values it pushes in are validated too.
+struct Validation<T> {
+    validators: Vec<Validator<T>>,
+    // Only created once a validator is added or someone asks for it, since most properties have
+    // no validators at all
+    error: Option<Observable<Option<String>>>,
+}
+
+impl<T> Validation<T> {
+    fn error(&mut self) -> &Observable<Option<String>> {
+        self.error.get_or_insert_with(|| Observable::new(None))
+    }
+}
+
+/// Run a value through a property's validators, reporting the outcome through its error
+/// observable (if it has one).
+fn validate<T>(validation: &RefCell<Validation<T>>, value: T) -> Result<T, ValidationError> {
+    let (result, error_ptr) = {
+        let validation = validation.borrow();
+        let result = validation.validators.iter().try_fold(value, |value, validator| validator(value));
+        (result, validation.error.as_ref().map(ObservablePtr::new))
+    };
+    // Error listeners may look at the property again, so they are only fired once we are done
+    if let Some(mut error_ptr) = error_ptr {
+        error_ptr.deref_mut().set(result.as_ref().err().map(|error| error.message().to_string()));
+    }
+    result
+}
+
+/// Clears a two-way binding's `updating` flag once a push ends, even if converting the value
+/// panics.
+struct UpdatingGuard<'a>(&'a Cell<bool>);
+
+impl<'a> Drop for UpdatingGuard<'a> {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+/// State shared by both sides of a two-way binding. `updating` is set while one side is pushing
+/// its value into the other, so that the other side doesn't push it right back.
+struct BidiLink {
+    active: Cell<bool>,
+    updating: Cell<bool>,
+}
+
+/// One side of a two-way binding, owned by a `Property<T>`. Dropping it (because the property was
+/// unbound, rebound, or dropped) deactivates the binding for the other side as well.
+struct BidiBinding<T> {
+    link: Rc<BidiLink>,
+    #[allow(dead_code)] // Needed to keep weak ref alive
+    handler: ChangeHandler<T>,
+}
+
+impl<T: 'static> BidiBinding<T> {
+    /// Create one side of a two-way binding, which pushes every change of this side's value,
+    /// converted, into `target`. The converted value has to get past the target's validators
+    /// first, otherwise the target keeps its current value.
+    fn new<U: 'static, F: 'static + Fn(&T) -> U>(link: Rc<BidiLink>, target: ObservablePtr<U>, target_validation: Weak<RefCell<Validation<U>>>, convert: F) -> Self {
+        let link_clone = link.clone();
+        let handler = ChangeHandler::new(move |_: &T, new: &T| {
+            let link = &link_clone;
+            if !link.active.get() || link.updating.get() {
+                return;
+            }
+            link.updating.set(true);
+            let _guard = UpdatingGuard(&link.updating);
+            let value = convert(new);
+            let value = match target_validation.upgrade() {
+                Some(validation) => validate(&validation, value),
+                None => Ok(value),
+            };
+            let mut target = target.clone();
+            if let (Ok(value), Some(mut obsv)) = (value, target.try_deref_mut()) {
+                obsv.set(value);
+            };
+        });
+        BidiBinding { link: link, handler: handler }
+    }
+}
+
+impl<T> Drop for BidiBinding<T> {
+    fn drop(&mut self) {
+        self.link.active.set(false);
+    }
 }
 
+/// A property's binding, which is shared with any expressions pointing at the property so they can
+/// refresh its value through it.
+type SharedBinding<T> = Rc<RefCell<Option<Binding<T>>>>;
+
+/// A property's side of a two-way binding, which is shared with its read-only views so they can
+/// tell whether it is bound.
+type SharedLink<T> = Rc<RefCell<Option<BidiBinding<T>>>>;
+
+type Validator<T> = Box<Fn(T) -> Result<T, ValidationError>>;
+
 /// A property is a value which can get set, queried, and chained.
 ///
 /// Properties are particularly useful for setting up a listening pattern, where one struct wants to
@@ -63,19 +170,50 @@ struct Binding<T: PartialEq> {
 /// assert_eq!(10, sum.get());
 /// assert_eq!(true, and_output.get());
 /// ```
-pub struct Property<T: PartialEq> {
+pub struct Property<T> {
     value: Observable<T>,
-    bound_to: Option<Binding<T>>,
+    bound_to: SharedBinding<T>,
+    linked_to: SharedLink<T>,
+    validation: Rc<RefCell<Validation<T>>>,
 }
 
-impl<T: 'static + PartialEq> Property<T> {
+impl<T: 'static> Property<T> {
     /// Create a new property, initialized with a target value
-    pub fn new(value: T) -> Property<T> {
-        Property { value: Observable::new(value), bound_to: None }
+    pub fn new(value: T) -> Property<T> where T: PartialEq {
+        Property::with_policy(value, PartialEqPolicy)
+    }
+
+    /// Create a new property which uses a custom policy to decide whether a new value is different
+    /// from the current one. This also allows creating properties for types that don't implement
+    /// `PartialEq`. See the `equality` module for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use std::rc::Rc;
+    /// use pebl::prelude::*;
+    /// use pebl::equality::PtrEq;
+    ///
+    /// struct Document { title: String } // Not PartialEq
+    ///
+    /// let doc = Rc::new(Document { title: String::from("Untitled") });
+    /// let mut current = Property::with_policy(doc.clone(), PtrEq);
+    /// let title = expr::unary(&current, |doc| doc.title.clone());
+    /// assert_eq!("Untitled", title.get());
+    ///
+    /// current.set(Rc::new(Document { title: String::from("Notes") }));
+    /// assert_eq!("Notes", title.get());
+    /// ```
+    pub fn with_policy<P: 'static + EqualityPolicy<T>>(value: T, policy: P) -> Property<T> {
+        Property {
+            value: Observable::with_policy(value, policy),
+            bound_to: Rc::new(RefCell::new(None)),
+            linked_to: Rc::new(RefCell::new(None)),
+            validation: Rc::new(RefCell::new(Validation { validators: Vec::new(), error: None })),
+        }
     }
 
     /// Create a new property, bound to a target expression and initialized with its value.
-    pub fn bound_to<E: IntoExpression<T>>(target: E) -> Property<T> {
+    pub fn bound_to<E: IntoExpression<T>>(target: E) -> Property<T> where T: PartialEq {
         let expr = target.into_expr();
         let mut p = Property::new(expr.get());
         p.bind_expr(expr);
@@ -85,19 +223,98 @@ impl<T: 'static + PartialEq> Property<T> {
     /// Return a reference to this property's value. If this property is bound, the value will be
     /// derived from the target expression.
     pub fn get(&self) -> &T {
-        if let Some(ref binding) = self.bound_to {
-            if binding.dirty.get() {
-                let mut value_ptr = ObservablePtr::new(&self.value);
-                value_ptr.deref_mut().set(binding.expr.get());
-                binding.dirty.set(false);
-            }
-        }
+        refresh(&ObservablePtr::new(&self.value), &self.bound_to);
         self.value.get()
     }
 
     /// Set the value of this property directly.
+    ///
+    /// If any validators were registered with `add_validator`, the value is passed through them
+    /// first, and silently dropped if one of them rejects it. Use `try_set` to find out why.
     pub fn set(&mut self, value: T) {
-        self.value.set(value)
+        let _ = self.try_set(value);
+    }
+
+    /// Set the value of this property, after running it through any validators registered with
+    /// `add_validator`. If a validator rejects the value, the property keeps its current value and
+    /// the error is returned (and also reported by `validation_error`).
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    /// use pebl::validate::ValidationError;
+    ///
+    /// let mut username = Property::new(String::from("guest"));
+    /// username.add_validator(|name: String| {
+    ///     if name.is_empty() { Err(ValidationError::new("required")) } else { Ok(name.to_lowercase()) }
+    /// });
+    ///
+    /// assert_eq!(Err(ValidationError::new("required")), username.try_set(String::new()));
+    /// assert_eq!("guest", *username.get());
+    ///
+    /// assert_eq!(Ok(()), username.try_set(String::from("Admin")));
+    /// assert_eq!("admin", *username.get());
+    /// ```
+    pub fn try_set(&mut self, value: T) -> Result<(), ValidationError> {
+        let value = validate(&self.validation, value)?;
+        self.value.set(value);
+        Ok(())
+    }
+
+    /// Register a validator which every value passed into `set` or `try_set` must go through
+    /// before it is stored. A validator can reject the value by returning an error, or accept it,
+    /// possibly after changing it (for example, to clamp it to some range). Validators run in the
+    /// order they were added. See the `validate` module for a few common ones.
+    ///
+    /// Values pushed in by a two-way binding are validated as well. If one is rejected, this
+    /// property keeps its current value and reports the error, while the other side keeps the
+    /// value it was given. Values pulled from a one-way binding, changed through `modify_inner`,
+    /// or copied over when a two-way binding is first set up, are not validated.
+    pub fn add_validator<F: 'static + Fn(T) -> Result<T, ValidationError>>(&mut self, validator: F) {
+        let mut validation = self.validation.borrow_mut();
+        validation.error();
+        validation.validators.push(Box::new(validator));
+    }
+
+    /// Return an expression for the message of the error produced by the last value rejected by a
+    /// validator, or `None` if the last value set was accepted.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    /// use pebl::validate;
+    ///
+    /// let mut age = Property::new(30);
+    /// age.add_validator(validate::in_range(0, 150));
+    /// let error_label = Property::bound_to(expr::unary(age.validation_error(), |error| error.clone().unwrap_or_default()));
+    ///
+    /// age.set(-1);
+    /// assert_eq!("must be between 0 and 150", *error_label.get());
+    ///
+    /// age.set(31);
+    /// assert_eq!("", *error_label.get());
+    /// ```
+    pub fn validation_error(&self) -> Rc<Expression<Option<String>>> {
+        Rc::new(PropertyExpression::new(self.validation.borrow_mut().error()))
+    }
+
+    /// Return an expression which is `true` as long as the last value set on this property was
+    /// accepted by its validators.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    /// use pebl::validate;
+    ///
+    /// let mut age = Property::new(30);
+    /// age.add_validator(validate::in_range(0, 150));
+    /// let can_submit = Property::bound_to(age.is_valid());
+    ///
+    /// age.set(200);
+    /// assert_eq!(false, *can_submit.get());
+    /// ```
+    pub fn is_valid(&self) -> Rc<Expression<bool>> {
+        expr::unary(self.validation_error(), |error| error.is_none())
     }
 
     /// Modify the property's data value in place.
@@ -136,60 +353,283 @@ impl<T: 'static + PartialEq> Property<T> {
         self.bind_expr(target.into_expr());
     }
 
-    /// Remove a binding previously established by `bind`. It is a no-op to call this method on an
-    /// unbound property.
+    /// Bind this property and another one to each other, so that setting either one updates the
+    /// other. This property starts out with the other property's value.
+    ///
+    /// Either property can call `unbind` to end the binding for both of them, after which each
+    /// keeps its current value. Binding bidirectionally replaces any existing binding on either
+    /// property.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    ///
+    /// let mut model = Property::new(String::from("Dark"));
+    /// let mut field = Property::new(String::new());
+    /// field.bind_bidirectional(&mut model);
+    /// assert_eq!("Dark", *field.get());
+    ///
+    /// field.set(String::from("Light"));
+    /// assert_eq!("Light", *model.get());
+    ///
+    /// model.set(String::from("System"));
+    /// assert_eq!("System", *field.get());
+    /// ```
+    pub fn bind_bidirectional(&mut self, other: &mut Property<T>) where T: Clone {
+        self.bind_bidirectional_with(other, T::clone, T::clone);
+    }
+
+    /// Like `bind_bidirectional`, but for two properties of different types. `to_other` converts
+    /// this property's value into the other's type, and `from_other` converts it back. This
+    /// property starts out with the other property's (converted) value.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    ///
+    /// let mut font_size = Property::new(12);
+    /// let mut font_size_text = Property::new(String::new());
+    /// font_size_text.bind_bidirectional_with(&mut font_size, |text| text.parse().unwrap_or(0), |size| size.to_string());
+    /// assert_eq!("12", *font_size_text.get());
+    ///
+    /// font_size_text.set(String::from("14"));
+    /// assert_eq!(14, *font_size.get());
+    /// ```
+    pub fn bind_bidirectional_with<U, F, B>(&mut self, other: &mut Property<U>, to_other: F, from_other: B)
+        where T: Clone, U: 'static + Clone, F: 'static + Fn(&T) -> U, B: 'static + Fn(&U) -> T {
+        self.unbind();
+        other.unbind();
+        let value = from_other(other.get());
+        self.value.set(value);
+
+        let link = Rc::new(BidiLink { active: Cell::new(true), updating: Cell::new(false) });
+        let this_side = BidiBinding::new(link.clone(), ObservablePtr::new(&other.value), Rc::downgrade(&other.validation), to_other);
+        let other_side = BidiBinding::new(link, ObservablePtr::new(&self.value), Rc::downgrade(&self.validation), from_other);
+        self.value.add_change_handler(&this_side.handler);
+        other.value.add_change_handler(&other_side.handler);
+        *self.linked_to.borrow_mut() = Some(this_side);
+        *other.linked_to.borrow_mut() = Some(other_side);
+    }
+
+    /// Remove a binding previously established by `bind` or `bind_bidirectional`. It is a no-op to
+    /// call this method on an unbound property.
     pub fn unbind(&mut self) {
-        self.bound_to = None;
+        *self.bound_to.borrow_mut() = None;
+        *self.linked_to.borrow_mut() = None;
     }
 
     /// Returns `true` if this property is currently bound to a target expression.
+    ///
+    /// A binding ends automatically when any source of its target expression is dropped. The
+    /// property keeps the last value the expression had.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    ///
+    /// let mut p_src = Property::new(1);
+    /// let p_dst = Property::bound_to(&p_src);
+    /// p_src.set(2);
+    /// drop(p_src);
+    ///
+    /// assert_eq!(false, p_dst.is_bound());
+    /// assert_eq!(2, *p_dst.get());
+    /// ```
     pub fn is_bound(&self) -> bool {
-        self.bound_to.is_some()
+        is_bound(&self.bound_to, &self.linked_to)
+    }
+
+    /// Register a listener which will be triggered with the old and new values whenever this
+    /// property's value changes.
+    ///
+    /// # Example
+    /// ```
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    /// use pebl::prelude::*;
+    ///
+    /// let mut name = Property::new(String::from("John"));
+    /// let history = Rc::new(RefCell::new(Vec::new()));
+    /// let history_clone = history.clone();
+    /// let handler = ChangeHandler::new(move |old: &String, _: &String| history_clone.borrow_mut().push(old.clone()));
+    /// name.add_change_handler(&handler);
+    ///
+    /// name.set(String::from("Jane"));
+    /// name.modify_inner().push_str(" Doe");
+    /// assert_eq!(vec!["John", "Jane"], *history.borrow());
+    /// ```
+    pub fn add_change_handler(&self, handler: &ChangeHandler<T>) where T: Clone {
+        self.value.add_change_handler(handler);
+    }
+
+    /// Return a read-only view of this property, which can be read, listened to, and converted into
+    /// an expression, but not set, bound, or modified.
+    ///
+    /// This is useful for models which want to expose their state without letting anyone else
+    /// change it. The model keeps the writable property private and hands out views instead. A
+    /// view doesn't borrow the property, so it can be stored anywhere, and the model can keep
+    /// changing the property while views of it are around.
+    ///
+    /// # Example
+    /// ```
+    /// use pebl::prelude::*;
+    ///
+    /// struct Download {
+    ///     progress: Property<u32>,
+    /// }
+    ///
+    /// impl Download {
+    ///     fn progress(&self) -> ReadOnlyProperty<u32> {
+    ///         self.progress.read_only()
+    ///     }
+    /// }
+    ///
+    /// let mut download = Download { progress: Property::new(0) };
+    /// let progress = download.progress();
+    /// let label = Property::bound_to(expr::unary(&progress, |p| format!("{}%", p)));
+    ///
+    /// download.progress.set(50);
+    /// assert_eq!(50, progress.get());
+    /// assert_eq!("50%", *label.get());
+    /// ```
+    pub fn read_only(&self) -> ReadOnlyProperty<T> {
+        ReadOnlyProperty {
+            value: ObservablePtr::new(&self.value),
+            binding: Rc::downgrade(&self.bound_to),
+            link: Rc::downgrade(&self.linked_to),
+        }
     }
 
     fn bind_expr(&mut self, expr: Rc<Expression<T>>) {
+        *self.linked_to.borrow_mut() = None;
         let dirty = Rc::new(Cell::new(true));
         let dirty_clone = dirty.clone();
-        let handle = InvalidationHandler::new(move || dirty_clone.set(true));
+        // Values are only pulled when read, but our own listeners should hear about the change now
+        let value_ptr = ObservablePtr::new(&self.value);
+        let handle = InvalidationHandler::new(move || {
+            dirty_clone.set(true);
+            if let Some(obsv) = value_ptr.try_deref() {
+                obsv.invalidate();
+            };
+        });
+
+        // Sources are still readable while their disposal handlers run, so grab the final value
+        // now in case we haven't pulled it yet
+        let disposed = Rc::new(Cell::new(false));
+        let final_value = Rc::new(RefCell::new(None));
+        let disposal_handle;
+        {
+            let expr = expr.clone();
+            let disposed = disposed.clone();
+            let final_value = final_value.clone();
+            disposal_handle = DisposalHandler::new(move || {
+                if !disposed.get() {
+                    *final_value.borrow_mut() = expr.try_get();
+                    disposed.set(true);
+                }
+            });
+        }
+
         let binding = Binding {
             expr: expr,
             handle: handle,
+            disposal_handle: disposal_handle,
             dirty: dirty,
+            disposed: disposed,
+            final_value: final_value,
         };
         binding.expr.add_invalidation_handler(&binding.handle);
-        self.bound_to = Some(binding);
+        binding.expr.add_disposal_handler(&binding.disposal_handle);
+        *self.bound_to.borrow_mut() = Some(binding);
+        ObservablePtr::new(&self.value).deref().invalidate();
+    }
+}
+
+/// Returns `true` if a property with the given bindings is currently bound.
+fn is_bound<T>(binding: &RefCell<Option<Binding<T>>>, link: &RefCell<Option<BidiBinding<T>>>) -> bool {
+    if let Some(ref side) = *link.borrow() {
+        return side.link.active.get();
+    }
+    match *binding.borrow() {
+        Some(ref binding) => !binding.disposed.get(),
+        None => false,
+    }
+}
+
+/// Pull the latest value of a property's binding into its observable, if it's out of date. Its
+/// listeners were already invalidated when the binding became dirty, so they aren't fired again.
+fn refresh<T>(value: &ObservablePtr<T>, binding: &RefCell<Option<Binding<T>>>) {
+    if let Some(ref binding) = *binding.borrow() {
+        if binding.disposed.get() {
+            if let Some(final_value) = binding.final_value.borrow_mut().take() {
+                value.clone().deref_mut().refresh(final_value);
+            }
+        } else if binding.dirty.get() {
+            let new_value = binding.expr.get();
+            binding.dirty.set(false);
+            value.clone().deref_mut().refresh(new_value);
+        }
     }
 }
 
 /// A simple expression which wraps a `Property<T>`s data and acts as a thin layer around it, simply
 /// returning its value directly. This class ultimately allows one property to bind to another
 /// (since properties bind to target expressions, not properties).
-struct PropertyExpression<T: PartialEq> {
+struct PropertyExpression<T> {
     src: ObservablePtr<T>,
+    binding: Weak<RefCell<Option<Binding<T>>>>,
 }
 
-impl<T: PartialEq> PropertyExpression<T> {
+impl<T> PropertyExpression<T> {
     pub fn new(src: &Observable<T>) -> Self {
-        PropertyExpression { src: ObservablePtr::new(src) }
+        PropertyExpression { src: ObservablePtr::new(src), binding: Weak::new() }
+    }
+
+    /// Create an expression for a property which may be bound, so reading the expression pulls in
+    /// the latest value of the property's binding first.
+    pub fn with_binding(src: &Observable<T>, binding: &SharedBinding<T>) -> Self {
+        PropertyExpression { src: ObservablePtr::new(src), binding: Rc::downgrade(binding) }
     }
 }
 
-impl<T: 'static + PartialEq + Clone> IntoExpression<T> for PropertyExpression<T> {
+impl<T: 'static + Clone> IntoExpression<T> for PropertyExpression<T> {
     fn into_expr(self) -> Rc<Expression<T>> {
         Rc::new(self)
     }
 }
 
-impl<T: 'static + PartialEq + Clone> Expression<T> for PropertyExpression<T> {
+impl<T: 'static + Clone> Expression<T> for PropertyExpression<T> {
     fn try_get(&self) -> Option<T> {
+        if let Some(binding) = self.binding.upgrade() {
+            refresh(&self.src, &binding);
+        }
         self.src.try_deref().map(|obsv| obsv.get().clone())
     }
 
     fn add_invalidation_handler(&self, handler: &InvalidationHandler) {
+        handler.raise_rank(self.height());
         if let Some(ref obsv) = self.src.try_deref() {
             obsv.add_invalidation_handler(handler);
         }
     }
+
+    /// A bound property only changes after the expression it's bound to, so it sits one above it
+    fn height(&self) -> usize {
+        match self.binding.upgrade() {
+            Some(binding) => match *binding.borrow() {
+                Some(ref binding) if !binding.disposed.get() => binding.expr.height() + 1,
+                _ => 0,
+            },
+            None => 0,
+        }
+    }
+
+    fn add_disposal_handler(&self, handler: &DisposalHandler) {
+        match self.src.try_deref() {
+            Some(ref obsv) => obsv.add_disposal_handler(handler),
+            None => handler.fire(), // Already gone
+        }
+    }
 }
 
 
@@ -216,7 +656,7 @@ impl<T: 'static + PartialEq + Default> Default for Property<T> {
     }
 }
 
-impl<T: PartialEq + Default> Property<T> {
+impl<T: Default> Property<T> {
     /// Convenience method for clearing properties whose type supports the `Default` trait
     ///
     /// # Example
@@ -251,18 +691,106 @@ impl Property<bool> {
     }
 }
 
-impl<'a, T: 'static + PartialEq + Clone> IntoExpression<T> for &'a Property<T> {
+impl<'a, T: 'static + Clone> IntoExpression<T> for &'a Property<T> {
     fn into_expr(self) -> Rc<Expression<T>> {
-        Rc::new(PropertyExpression::new(&self.value))
+        Rc::new(PropertyExpression::with_binding(&self.value, &self.bound_to))
     }
 }
 
-impl<'a, T: 'static + PartialEq + Clone> CoreExpressions<T> for &'a Property<T> {
+impl<'a, T: 'static + Clone> CoreExpressions<T> for &'a Property<T> {
     // Default implementations are fine
 }
 
-impl<T: 'static + fmt::Debug + PartialEq> fmt::Debug for Property<T> {
+impl<T: 'static + fmt::Debug> fmt::Debug for Property<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Property {{ {:?} }}", self.get())
     }
 }
+
+/// A read-only view of a `Property<T>`, created by `Property::read_only`.
+///
+/// A view only points at the property, without keeping it alive. Once the property is dropped,
+/// `try_get` returns `None`, and expressions made from the view stop producing values.
+pub struct ReadOnlyProperty<T> {
+    value: ObservablePtr<T>,
+    binding: Weak<RefCell<Option<Binding<T>>>>,
+    link: Weak<RefCell<Option<BidiBinding<T>>>>,
+}
+
+impl<T: 'static> ReadOnlyProperty<T> {
+    /// Return a copy of the underlying property's value. Like `Property::get`, if the property is
+    /// bound, the value will be derived from the target expression.
+    ///
+    /// Unlike `Property::get`, this can't hand out a reference, since the view doesn't stop the
+    /// property's owner from setting a new value while the reference is still around.
+    ///
+    /// This panics if the property was dropped. Use `try_get` if that can happen.
+    pub fn get(&self) -> T where T: Clone {
+        self.try_get().expect("Property behind this view was dropped")
+    }
+
+    /// Return a copy of the underlying property's value, or `None` if the property was dropped.
+    pub fn try_get(&self) -> Option<T> where T: Clone {
+        self.refresh();
+        self.value.try_deref().map(|value| value.get().clone())
+    }
+
+    /// Pull in the latest value of the underlying property's binding, if it has one.
+    fn refresh(&self) {
+        if let Some(binding) = self.binding.upgrade() {
+            refresh(&self.value, &binding);
+        }
+    }
+
+    /// Returns `true` if the underlying property is currently bound to a target expression.
+    pub fn is_bound(&self) -> bool {
+        match (self.binding.upgrade(), self.link.upgrade()) {
+            (Some(binding), Some(link)) => is_bound(&binding, &link),
+            _ => false,
+        }
+    }
+
+    /// Register a listener which will be triggered with the old and new values whenever the
+    /// underlying property's value changes.
+    pub fn add_change_handler(&self, handler: &ChangeHandler<T>) where T: Clone {
+        if let Some(obsv) = self.value.try_deref() {
+            obsv.add_change_handler(handler);
+        }
+    }
+}
+
+impl<T> Clone for ReadOnlyProperty<T> {
+    fn clone(&self) -> Self {
+        ReadOnlyProperty { value: self.value.clone(), binding: self.binding.clone(), link: self.link.clone() }
+    }
+}
+
+impl<T: 'static + Clone> IntoExpression<T> for ReadOnlyProperty<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        Rc::new(PropertyExpression { src: self.value, binding: self.binding })
+    }
+}
+
+impl<'a, T: 'static + Clone> IntoExpression<T> for &'a ReadOnlyProperty<T> {
+    fn into_expr(self) -> Rc<Expression<T>> {
+        self.clone().into_expr()
+    }
+}
+
+impl<T: 'static + Clone> CoreExpressions<T> for ReadOnlyProperty<T> {
+    // Default implementations are fine
+}
+
+impl<'a, T: 'static + Clone> CoreExpressions<T> for &'a ReadOnlyProperty<T> {
+    // Default implementations are fine
+}
+
+impl<T: 'static + fmt::Debug> fmt::Debug for ReadOnlyProperty<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.refresh();
+        match self.value.try_deref() {
+            Some(value) => write!(f, "ReadOnlyProperty {{ {:?} }}", value.get()),
+            None => write!(f, "ReadOnlyProperty {{ null }}"),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Thread-safe expressions, which mirror `expr::Expression<T>` but can be shared across threads.
+
+use std::sync::Arc;
+
+use sync::obsv::{SyncDisposalHandler, SyncInvalidationHandler};
+
+pub trait IntoSyncExpression<T> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<T>>;
+}
+
+impl<T> IntoSyncExpression<T> for Arc<SyncExpression<T>> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<T>> {
+        self
+    }
+}
+
+pub trait SyncExpression<T>: IntoSyncExpression<T> + Send + Sync {
+    fn try_get(&self) -> Option<T>;
+    fn get(&self) -> T {
+        self.try_get().unwrap()
+    }
+    fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler);
+
+    /// Register a listener which will be triggered when a source of this expression is dropped.
+    /// Expressions without any sources can rely on this default, which never fires.
+    fn add_disposal_handler(&self, _handler: &SyncDisposalHandler) {}
+}
+
+/// Create a thread-safe expression which transforms the value of a single source expression.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use pebl::sync::{self, SyncProperty};
+///
+/// let p = Arc::new(SyncProperty::new(10));
+/// let doubled = sync::expr::unary(&*p, |val| val * 2);
+///
+/// let p_clone = p.clone();
+/// thread::spawn(move || p_clone.set(21)).join().unwrap();
+/// assert_eq!(42, doubled.get());
+/// ```
+pub fn unary<I, O, E, F>(src: E, f: F) -> Arc<SyncExpression<O>>
+    where I: 'static, O: 'static, E: IntoSyncExpression<I>, F: 'static + Fn(&I) -> O + Send + Sync {
+    Arc::new(SyncUnaryExpression {
+        src: src.into_sync_expr(),
+        f: Box::new(f),
+    })
+}
+
+/// Create a thread-safe expression which combines the values of two source expressions.
+pub fn binary<I1, I2, O, E1, E2, F>(lhs: E1, rhs: E2, f: F) -> Arc<SyncExpression<O>>
+    where I1: 'static, I2: 'static, O: 'static, E1: IntoSyncExpression<I1>, E2: IntoSyncExpression<I2>, F: 'static + Fn(&I1, &I2) -> O + Send + Sync {
+    Arc::new(SyncBinaryExpression {
+        lhs: lhs.into_sync_expr(),
+        rhs: rhs.into_sync_expr(),
+        f: Box::new(f),
+    })
+}
+
+pub struct SyncUnaryExpression<I: 'static, O: 'static> {
+    src: Arc<SyncExpression<I>>,
+    f: Box<Fn(&I) -> O + Send + Sync>,
+}
+
+impl<I: 'static, O: 'static> IntoSyncExpression<O> for SyncUnaryExpression<I, O> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<O>> {
+        Arc::new(self)
+    }
+}
+
+impl<I: 'static, O: 'static> SyncExpression<O> for SyncUnaryExpression<I, O> {
+    fn try_get(&self) -> Option<O> {
+        self.src.try_get().map(|val| (self.f)(&val))
+    }
+
+    fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        self.src.add_invalidation_handler(handler);
+    }
+
+    fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        self.src.add_disposal_handler(handler);
+    }
+}
+
+pub struct SyncBinaryExpression<I1: 'static, I2: 'static, O: 'static> {
+    lhs: Arc<SyncExpression<I1>>,
+    rhs: Arc<SyncExpression<I2>>,
+    f: Box<Fn(&I1, &I2) -> O + Send + Sync>,
+}
+
+impl<I1: 'static, I2: 'static, O: 'static> IntoSyncExpression<O> for SyncBinaryExpression<I1, I2, O> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<O>> {
+        Arc::new(self)
+    }
+}
+
+impl<I1: 'static, I2: 'static, O: 'static> SyncExpression<O> for SyncBinaryExpression<I1, I2, O> {
+    fn try_get(&self) -> Option<O> {
+        let (lhs_opt, rhs_opt) = (self.lhs.try_get(), self.rhs.try_get());
+        if lhs_opt.is_none() || rhs_opt.is_none() { return None }
+        Some((self.f)(&lhs_opt.unwrap(), &rhs_opt.unwrap()))
+    }
+
+    fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        self.lhs.add_invalidation_handler(handler);
+        self.rhs.add_invalidation_handler(handler);
+    }
+
+    fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        self.lhs.add_disposal_handler(handler);
+        self.rhs.add_disposal_handler(handler);
+    }
+}
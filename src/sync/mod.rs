@@ -0,0 +1,23 @@
+//! Thread-safe counterparts to the core types of this crate.
+//!
+//! The regular `Observable<T>` and `Property<T>` types are built on `Rc` and are confined to the
+//! thread that created them, which keeps them cheap. The types in this module are built on `Arc`
+//! and locks instead, so they can be shared freely between threads (usually by wrapping them in an
+//! `Arc` themselves). Since all state lives behind locks, their methods only need `&self`.
+//!
+//! The API mirrors the single-threaded one: observables and properties accept an equality policy
+//! through `with_policy`, and dropping them fires their disposal handlers rather than their
+//! invalidation handlers.
+//!
+//! Note that invalidation handlers registered with these types fire on whichever thread changed
+//! the value, and they do not participate in `notify::batch`, which is a per-thread concept.
+
+pub mod expr;
+pub mod obsv;
+pub mod property;
+pub mod weak;
+
+pub use self::expr::{IntoSyncExpression, SyncExpression};
+pub use self::obsv::{SyncDisposalHandler, SyncInvalidationHandler, SyncObservable};
+pub use self::property::SyncProperty;
+pub use self::weak::SyncWeakList;
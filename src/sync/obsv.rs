@@ -0,0 +1,250 @@
+//! A module supporting the `SyncObservable<T>` struct, a thread-safe version of `Observable<T>`.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+use equality::{EqualityPolicy, PartialEqPolicy};
+use sync::weak::SyncWeakList;
+
+/// A callback which gets fired when a target `SyncObservable<T>` changes. The callback may be
+/// called from any thread.
+pub struct SyncInvalidationHandler {
+    callback: Arc<Fn() + Send + Sync>,
+}
+
+impl SyncInvalidationHandler {
+    pub fn new<F: 'static + Fn() + Send + Sync>(callback: F) -> Self {
+        SyncInvalidationHandler { callback: Arc::new(callback) }
+    }
+}
+
+/// A callback which gets fired when a target `SyncObservable<T>` is dropped. This mirrors
+/// `obsv::DisposalHandler`, and the callback may be called from any thread.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use pebl::sync::{SyncDisposalHandler, SyncObservable};
+///
+/// let val = SyncObservable::new(42);
+/// let disposed = Arc::new(AtomicBool::new(false));
+/// let disposed_clone = disposed.clone();
+/// let handler = SyncDisposalHandler::new(move || disposed_clone.store(true, Ordering::SeqCst));
+/// val.add_disposal_handler(&handler);
+///
+/// assert!(!disposed.load(Ordering::SeqCst));
+/// drop(val);
+/// assert!(disposed.load(Ordering::SeqCst));
+/// ```
+pub struct SyncDisposalHandler {
+    callback: Arc<Fn() + Send + Sync>,
+}
+
+impl SyncDisposalHandler {
+    pub fn new<F: 'static + Fn() + Send + Sync>(callback: F) -> Self {
+        SyncDisposalHandler { callback: Arc::new(callback) }
+    }
+}
+
+/// Core data which is shared by a `SyncObservable<T>` and any expressions pointing at it.
+pub(crate) struct SyncObservableData<T> {
+    value: RwLock<T>,
+    policy: Box<EqualityPolicy<T> + Send + Sync>,
+    on_invalidated: SyncWeakList<Fn() + Send + Sync>,
+    on_disposed: SyncWeakList<Fn() + Send + Sync>,
+}
+
+impl<T> SyncObservableData<T> {
+    pub(crate) fn read(&self) -> RwLockReadGuard<T> {
+        self.value.read().unwrap()
+    }
+
+    pub(crate) fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        self.on_invalidated.push(&handler.callback);
+    }
+
+    pub(crate) fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        self.on_disposed.push(&handler.callback);
+    }
+
+    /// Trigger the invalidation handlers of any listeners. Never call this while holding a lock on
+    /// the value, or listeners which query it will deadlock.
+    fn fire_invalidated(&self) {
+        for callback in self.on_invalidated.upgrade() {
+            callback();
+        }
+    }
+}
+
+/// A data value which can be shared across threads and which can have listeners attached that are
+/// triggered when it changes.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+/// use pebl::sync::{SyncInvalidationHandler, SyncObservable};
+///
+/// let val = Arc::new(SyncObservable::new(42));
+/// let times_modified = Arc::new(AtomicUsize::new(0));
+/// let times_modified_clone = times_modified.clone();
+/// let handler = SyncInvalidationHandler::new(move || {
+///     times_modified_clone.fetch_add(1, Ordering::SeqCst);
+/// });
+/// val.add_invalidation_handler(&handler);
+///
+/// let val_clone = val.clone();
+/// thread::spawn(move || val_clone.set(9001)).join().unwrap();
+///
+/// assert_eq!(9001, *val.get());
+/// assert_eq!(1, times_modified.load(Ordering::SeqCst));
+/// ```
+pub struct SyncObservable<T> {
+    data: Arc<SyncObservableData<T>>,
+}
+
+impl<T> SyncObservable<T> {
+    /// Create a new observable
+    pub fn new(value: T) -> Self where T: PartialEq {
+        SyncObservable::with_policy(value, PartialEqPolicy)
+    }
+
+    /// Create a new observable which uses a custom policy to decide whether a new value is
+    /// different from the current one. See the `equality` module for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use pebl::equality::Approx;
+    /// use pebl::sync::{SyncInvalidationHandler, SyncObservable};
+    ///
+    /// let val = SyncObservable::with_policy(1.0, Approx(0.1));
+    /// let count = Arc::new(AtomicUsize::new(0));
+    /// let count_clone = count.clone();
+    /// let handler = SyncInvalidationHandler::new(move || { count_clone.fetch_add(1, Ordering::SeqCst); });
+    /// val.add_invalidation_handler(&handler);
+    ///
+    /// val.set(1.01);
+    /// assert_eq!(0, count.load(Ordering::SeqCst));
+    /// val.set(2.0);
+    /// assert_eq!(1, count.load(Ordering::SeqCst));
+    /// ```
+    pub fn with_policy<P: 'static + EqualityPolicy<T> + Send + Sync>(value: T, policy: P) -> Self {
+        let data = SyncObservableData {
+            value: RwLock::new(value),
+            policy: Box::new(policy),
+            on_invalidated: SyncWeakList::with_capacity(1),
+            on_disposed: SyncWeakList::new(),
+        };
+        SyncObservable { data: Arc::new(data) }
+    }
+
+    /// Get a read guard to the value contained by this observable. Other threads will block on
+    /// setting this value while the guard is alive.
+    pub fn get(&self) -> RwLockReadGuard<T> {
+        self.data.read()
+    }
+
+    /// Set the value of this observable. If the value changes, it will trigger any listeners that
+    /// have been registered with `add_invalidation_handler`.
+    pub fn set(&self, value: T) {
+        let changed = {
+            let mut current = self.data.value.write().unwrap();
+            if !self.data.policy.equals(&current, &value) {
+                *current = value;
+                true
+            } else {
+                false
+            }
+        };
+
+        if changed {
+            self.data.fire_invalidated();
+        }
+    }
+
+    /// Modify the existing value of this observable instead of overwriting it. Like
+    /// `Observable::modify_inner`, this always fires any registered listeners once the returned
+    /// guard is dropped.
+    #[must_use]
+    pub fn modify_inner(&self) -> SyncModifyInnerRef<T> {
+        SyncModifyInnerRef { guard: Some(self.data.value.write().unwrap()), data: &self.data }
+    }
+
+    /// Register a listener which will be triggered whenever this observable's value is updated.
+    pub fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        self.data.add_invalidation_handler(handler);
+    }
+
+    /// Register a listener which will be triggered when this observable is dropped.
+    pub fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        self.data.add_disposal_handler(handler);
+    }
+
+    pub(crate) fn downgrade(&self) -> Weak<SyncObservableData<T>> {
+        Arc::downgrade(&self.data)
+    }
+}
+
+impl<T> Drop for SyncObservable<T> {
+    fn drop(&mut self) {
+        // Sources are still readable while disposal handlers run, as our data hasn't been dropped
+        for callback in self.data.on_disposed.upgrade() {
+            callback();
+        }
+    }
+}
+
+impl<T: Default + PartialEq> Default for SyncObservable<T> {
+    fn default() -> Self {
+        SyncObservable::new(Default::default())
+    }
+}
+
+impl<T: Default> SyncObservable<T> {
+    /// If this observable supports the `Default` trait, call this convenience method to reset it to
+    /// its default value.
+    pub fn clear(&self) {
+        self.set(Default::default());
+    }
+}
+
+/// Intermediate struct used to handle calls to `SyncObservable::modify_inner`. It holds a write
+/// lock on the value, and fires any registered listeners after releasing it when dropped.
+pub struct SyncModifyInnerRef<'a, T: 'a> {
+    // Only `None` while being dropped
+    guard: Option<RwLockWriteGuard<'a, T>>,
+    data: &'a SyncObservableData<T>,
+}
+
+impl<'a, T: 'a> Deref for SyncModifyInnerRef<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<'a, T: 'a> DerefMut for SyncModifyInnerRef<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T: 'a> Drop for SyncModifyInnerRef<'a, T> {
+    fn drop(&mut self) {
+        self.guard = None; // Release the lock before notifying listeners
+        self.data.fire_invalidated();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SyncObservable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncObservable {{ {:?} }}", *self.get())
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, RwLockReadGuard, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use sync::expr::{IntoSyncExpression, SyncExpression};
+use equality::EqualityPolicy;
+use sync::obsv::{SyncDisposalHandler, SyncInvalidationHandler, SyncModifyInnerRef, SyncObservable, SyncObservableData};
+
+/// Data for linking to some target `SyncExpression<T>`. When the expression's value changes, this
+/// struct's `dirty` value will be set to `true`.
+///
+/// If a source of the expression is dropped, the binding is `disposed` and stops tracking it,
+/// holding onto the last value the expression produced.
+struct SyncBinding<T> {
+    expr: Arc<SyncExpression<T>>,
+    #[allow(dead_code)] // Needed to keep weak ref alive
+    handle: SyncInvalidationHandler,
+    #[allow(dead_code)] // Needed to keep weak ref alive
+    disposal_handle: SyncDisposalHandler,
+    dirty: Arc<AtomicBool>,
+    disposed: Arc<AtomicBool>,
+    final_value: Arc<Mutex<Option<T>>>,
+}
+
+/// A thread-safe version of `Property<T>`.
+///
+/// A sync property can be wrapped in an `Arc` and handed to worker threads, which can then `set`
+/// it while other threads read it or have bound to it.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use pebl::sync::SyncProperty;
+///
+/// let p_src = Arc::new(SyncProperty::new(10));
+/// let p_dst = SyncProperty::bound_to(&*p_src);
+/// assert_eq!(10, *p_dst.get());
+///
+/// let p_src_clone = p_src.clone();
+/// thread::spawn(move || p_src_clone.set(20)).join().unwrap();
+/// assert_eq!(20, *p_dst.get());
+/// ```
+pub struct SyncProperty<T> {
+    value: SyncObservable<T>,
+    bound_to: Mutex<Option<SyncBinding<T>>>,
+}
+
+impl<T: 'static + Send + Sync> SyncProperty<T> {
+    /// Create a new property, initialized with a target value
+    pub fn new(value: T) -> SyncProperty<T> where T: PartialEq {
+        SyncProperty { value: SyncObservable::new(value), bound_to: Mutex::new(None) }
+    }
+
+    /// Create a new property which uses a custom policy to decide whether a new value is different
+    /// from the current one. This also allows creating sync properties for types that don't
+    /// implement `PartialEq`. See the `equality` module for more details.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use pebl::equality::PtrEq;
+    /// use pebl::sync::SyncProperty;
+    ///
+    /// struct Document { title: String } // Not PartialEq
+    ///
+    /// let current = SyncProperty::with_policy(Arc::new(Document { title: String::from("Untitled") }), PtrEq);
+    /// assert_eq!("Untitled", current.get().title);
+    ///
+    /// current.set(Arc::new(Document { title: String::from("Notes") }));
+    /// assert_eq!("Notes", current.get().title);
+    /// ```
+    pub fn with_policy<P: 'static + EqualityPolicy<T> + Send + Sync>(value: T, policy: P) -> SyncProperty<T> {
+        SyncProperty { value: SyncObservable::with_policy(value, policy), bound_to: Mutex::new(None) }
+    }
+
+    /// Create a new property, bound to a target expression and initialized with its value.
+    pub fn bound_to<E: IntoSyncExpression<T>>(target: E) -> SyncProperty<T> where T: PartialEq {
+        let expr = target.into_sync_expr();
+        let p = SyncProperty::new(expr.get());
+        p.bind_expr(expr);
+        p
+    }
+
+    /// Return a read guard to this property's value. If this property is bound, the value will be
+    /// derived from the target expression.
+    pub fn get(&self) -> RwLockReadGuard<T> {
+        // Evaluate the binding without holding any locks, in case it reads this property's
+        // listeners or other properties bound to it
+        let (final_value, expr) = match *self.bound_to.lock().unwrap() {
+            Some(ref binding) if binding.disposed.load(Ordering::SeqCst) => (binding.final_value.lock().unwrap().take(), None),
+            Some(ref binding) if binding.dirty.swap(false, Ordering::SeqCst) => (None, Some(binding.expr.clone())),
+            _ => (None, None),
+        };
+        // A source may be dropped between checking the binding and evaluating it
+        if let Some(value) = final_value.or_else(|| expr.and_then(|expr| expr.try_get())) {
+            self.value.set(value);
+        }
+        self.value.get()
+    }
+
+    /// Set the value of this property directly.
+    pub fn set(&self, value: T) {
+        self.value.set(value)
+    }
+
+    /// Modify the property's data value in place.
+    pub fn modify_inner(&self) -> SyncModifyInnerRef<T> {
+        self.value.modify_inner()
+    }
+
+    /// Bind this property to some target expression.
+    pub fn bind<E: IntoSyncExpression<T>>(&self, target: E) {
+        self.bind_expr(target.into_sync_expr());
+    }
+
+    /// Remove a binding previously established by `bind`. It is a no-op to call this method on an
+    /// unbound property.
+    pub fn unbind(&self) {
+        *self.bound_to.lock().unwrap() = None;
+    }
+
+    /// Returns `true` if this property is currently bound to a target expression.
+    pub fn is_bound(&self) -> bool {
+        match *self.bound_to.lock().unwrap() {
+            Some(ref binding) => !binding.disposed.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    /// Register a listener which will be triggered whenever this property's value is updated.
+    pub fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        self.value.add_invalidation_handler(handler);
+    }
+
+    /// Register a listener which will be triggered when this property is dropped.
+    pub fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        self.value.add_disposal_handler(handler);
+    }
+
+    fn bind_expr(&self, expr: Arc<SyncExpression<T>>) {
+        let dirty = Arc::new(AtomicBool::new(true));
+        let dirty_clone = dirty.clone();
+        let handle = SyncInvalidationHandler::new(move || dirty_clone.store(true, Ordering::SeqCst));
+
+        // Sources are still readable while their disposal handlers run, so grab the final value
+        // now in case we haven't pulled it yet
+        let disposed = Arc::new(AtomicBool::new(false));
+        let final_value = Arc::new(Mutex::new(None));
+        let disposal_handle;
+        {
+            let expr = expr.clone();
+            let disposed = disposed.clone();
+            let final_value = final_value.clone();
+            disposal_handle = SyncDisposalHandler::new(move || {
+                if !disposed.load(Ordering::SeqCst) {
+                    *final_value.lock().unwrap() = expr.try_get();
+                    disposed.store(true, Ordering::SeqCst);
+                }
+            });
+        }
+
+        let binding = SyncBinding {
+            expr: expr,
+            handle: handle,
+            disposal_handle: disposal_handle,
+            dirty: dirty,
+            disposed: disposed,
+            final_value: final_value,
+        };
+        binding.expr.add_invalidation_handler(&binding.handle);
+        binding.expr.add_disposal_handler(&binding.disposal_handle);
+        *self.bound_to.lock().unwrap() = Some(binding);
+    }
+}
+
+/// A thread-safe expression which wraps a `SyncProperty<T>`s data, allowing other sync properties
+/// to bind to it.
+struct SyncPropertyExpression<T> {
+    src: Weak<SyncObservableData<T>>,
+}
+
+impl<T: 'static + Clone + Send + Sync> IntoSyncExpression<T> for SyncPropertyExpression<T> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<T>> {
+        Arc::new(self)
+    }
+}
+
+impl<T: 'static + Clone + Send + Sync> SyncExpression<T> for SyncPropertyExpression<T> {
+    fn try_get(&self) -> Option<T> {
+        self.src.upgrade().map(|data| data.read().clone())
+    }
+
+    fn add_invalidation_handler(&self, handler: &SyncInvalidationHandler) {
+        if let Some(data) = self.src.upgrade() {
+            data.add_invalidation_handler(handler);
+        }
+    }
+
+    fn add_disposal_handler(&self, handler: &SyncDisposalHandler) {
+        if let Some(data) = self.src.upgrade() {
+            data.add_disposal_handler(handler);
+        }
+    }
+}
+
+impl<T: 'static + PartialEq + Default + Send + Sync> Default for SyncProperty<T> {
+    fn default() -> Self {
+        SyncProperty::new(Default::default())
+    }
+}
+
+impl<T: Default> SyncProperty<T> {
+    /// Convenience method for clearing properties whose type supports the `Default` trait
+    pub fn clear(&self) {
+        self.value.clear();
+    }
+}
+
+impl<'a, T: 'static + Clone + Send + Sync> IntoSyncExpression<T> for &'a SyncProperty<T> {
+    fn into_sync_expr(self) -> Arc<SyncExpression<T>> {
+        Arc::new(SyncPropertyExpression { src: self.value.downgrade() })
+    }
+}
+
+impl<T: 'static + fmt::Debug + Send + Sync> fmt::Debug for SyncProperty<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SyncProperty {{ {:?} }}", *self.get())
+    }
+}
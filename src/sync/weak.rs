@@ -0,0 +1,88 @@
+//! A module which contains the `SyncWeakList<T>` class, a thread-safe version of `WeakList<T>`.
+
+use std::sync::{Arc, Mutex, Weak};
+
+/// A vector of weak references which can be shared across threads, and which will automatically
+/// be cleaned up when the values being referenced are dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use pebl::sync::SyncWeakList;
+///
+/// let list = SyncWeakList::<i32>::new();
+/// {
+///     let item1 = Arc::new(1);
+///     let item2 = Arc::new(2);
+///     list.push(&item1);
+///     list.push(&item2);
+///     assert_eq!(2, list.len());
+/// }
+/// assert_eq!(0, list.len());
+/// ```
+pub struct SyncWeakList<T: ? Sized> {
+    items: Mutex<Vec<Weak<T>>>,
+}
+
+impl<T: ? Sized> Default for SyncWeakList<T> {
+    fn default() -> Self {
+        SyncWeakList::new()
+    }
+}
+
+impl<T: ? Sized> SyncWeakList<T> {
+    /// Construct a new, empty list.
+    pub fn new() -> Self {
+        SyncWeakList::<T>::with_capacity(0)
+    }
+
+    /// Construct a new list with initial capacity, similar to `Vec<T>.with_capacity()`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SyncWeakList { items: Mutex::new(Vec::with_capacity(capacity)) }
+    }
+
+    /// Construct a new list, populated with initial values.
+    pub fn of(items: &[Arc<T>]) -> Self {
+        let weak_vec: Vec<Weak<T>> = items.iter().map(Arc::downgrade).collect();
+        SyncWeakList { items: Mutex::new(weak_vec) }
+    }
+
+    /// Add a value to the tail-end of this list. Unlike `WeakList<T>`, this only needs a shared
+    /// reference, since the list is protected by a lock.
+    pub fn push(&self, item: &Arc<T>) {
+        self.items.lock().unwrap().push(Arc::downgrade(item));
+    }
+
+    /// Create a copy of this weak list that holds only its strong references.
+    ///
+    /// The lock is released before this method returns, so it is safe to call back into this list
+    /// while iterating over the result.
+    pub fn upgrade(&self) -> Vec<Arc<T>> {
+        let mut items = self.items.lock().unwrap();
+        items.retain(|o| o.upgrade().is_some());
+        items.iter().filter_map(|o| o.upgrade()).collect()
+    }
+
+    /// Consume this weak list, converting into a strong list.
+    pub fn upgrade_owned(self) -> Vec<Arc<T>> {
+        self.upgrade()
+    }
+
+    /// Return the number of *strong* references in this list.
+    pub fn len(&self) -> usize {
+        let mut items = self.items.lock().unwrap();
+        items.retain(|o| o.upgrade().is_some());
+        items.len()
+    }
+
+    /// Returns `true` if this list holds no *strong* references.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the capacity of this list, similar to [`Vec<T>`][`capacity`]
+    pub fn capacity(&self) -> usize {
+        self.items.lock().unwrap().capacity()
+    }
+}
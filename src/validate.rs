@@ -0,0 +1,94 @@
+//! A module supporting validation of values before they are stored in a `Property<T>`.
+//!
+//! A validator is any `Fn(T) -> Result<T, ValidationError>` closure. It can reject a value by
+//! returning an error, or accept it, possibly after changing it (for example, clamping it to a
+//! range). A few common validators are provided here for convenience.
+
+use std::error::Error;
+use std::fmt;
+
+/// An error describing why a value was rejected by a validator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    message: String,
+}
+
+impl ValidationError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        ValidationError { message: message.into() }
+    }
+
+    /// A human readable description of the problem, suitable for showing next to a form field.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Create a validator which rejects any value outside of `min..=max`.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+/// use pebl::validate;
+///
+/// let mut age = Property::new(30);
+/// age.add_validator(validate::in_range(0, 150));
+///
+/// assert!(age.try_set(200).is_err());
+/// assert_eq!(30, *age.get());
+/// ```
+pub fn in_range<T: PartialOrd + fmt::Display>(min: T, max: T) -> impl Fn(T) -> Result<T, ValidationError> {
+    move |value| {
+        if value < min || value > max {
+            Err(ValidationError::new(format!("must be between {} and {}", min, max)))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Create a validator which never rejects a value, but clamps it to `min..=max` instead.
+///
+/// # Example
+///
+/// ```
+/// use pebl::prelude::*;
+/// use pebl::validate;
+///
+/// let mut volume = Property::new(5);
+/// volume.add_validator(validate::clamp(0, 10));
+///
+/// volume.set(11);
+/// assert_eq!(10, *volume.get());
+/// ```
+pub fn clamp<T: PartialOrd + Clone>(min: T, max: T) -> impl Fn(T) -> Result<T, ValidationError> {
+    move |value| {
+        if value < min {
+            Ok(min.clone())
+        } else if value > max {
+            Ok(max.clone())
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Create a validator which rejects empty (or whitespace only) strings.
+pub fn not_empty() -> impl Fn(String) -> Result<String, ValidationError> {
+    |value: String| {
+        if value.trim().is_empty() {
+            Err(ValidationError::new("must not be empty"))
+        } else {
+            Ok(value)
+        }
+    }
+}
@@ -0,0 +1,235 @@
+#[macro_use]
+extern crate spectral;
+extern crate pebl;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use spectral::prelude::*;
+use pebl::prelude::*;
+use pebl::expr::IntoExpression;
+
+#[test]
+fn computed_tracks_what_it_reads() {
+    let mut a = Property::new(1);
+    let mut b = Property::new(2);
+    let mut c = Property::new(3);
+    let result;
+    {
+        let (a, b, c) = ((&a).into_expr(), (&b).into_expr(), (&c).into_expr());
+        result = computed(move || a.get() + b.get() * c.get());
+    }
+    assert_that(&result.get()).is_equal_to(&7);
+
+    a.set(10);
+    assert_that(&result.get()).is_equal_to(&16);
+    b.set(0);
+    assert_that(&result.get()).is_equal_to(&10);
+    c.set(100);
+    assert_that(&result.get()).is_equal_to(&10);
+}
+
+#[test]
+fn computed_caches_until_a_dependency_changes() {
+    let mut a = Property::new(1);
+    let evaluations = Rc::new(Cell::new(0));
+    let doubled;
+    {
+        let a = (&a).into_expr();
+        let evaluations = evaluations.clone();
+        doubled = computed(move || {
+            evaluations.set(evaluations.get() + 1);
+            a.get() * 2
+        });
+    }
+
+    assert_that(&doubled.get()).is_equal_to(&2);
+    assert_that(&doubled.get()).is_equal_to(&2);
+    assert_that(&evaluations.get()).is_equal_to(&1);
+
+    a.set(2);
+    assert_that(&doubled.get()).is_equal_to(&4);
+    assert_that(&evaluations.get()).is_equal_to(&2);
+}
+
+#[test]
+fn computed_retracks_dependencies_on_each_run() {
+    let mut flag = Property::new(true);
+    let mut x = Property::new(1);
+    let mut y = Property::new(2);
+    let chosen;
+    {
+        let (flag, x, y) = ((&flag).into_expr(), (&x).into_expr(), (&y).into_expr());
+        chosen = computed(move || if flag.get() { x.get() } else { y.get() });
+    }
+    let mut l = Listeners::new();
+    let count = Rc::new(Cell::new(0));
+    {
+        let count = count.clone();
+        l.listen_to(chosen.clone()).with(move || count.set(count.get() + 1));
+    }
+    assert_that(&chosen.get()).is_equal_to(&1);
+
+    y.set(20); // Not read yet
+    assert_that(&count.get()).is_equal_to(&0);
+
+    flag.set(false);
+    assert_that(&count.get()).is_equal_to(&1);
+    assert_that(&chosen.get()).is_equal_to(&20);
+
+    x.set(10); // No longer read
+    assert_that(&count.get()).is_equal_to(&1);
+    y.set(30);
+    assert_that(&count.get()).is_equal_to(&2);
+    assert_that(&chosen.get()).is_equal_to(&30);
+}
+
+#[test]
+fn computed_can_read_other_expressions() {
+    let mut a = Property::new(1);
+    let mut b = Property::new(2);
+    let sum = a.plus(&b);
+    let sum_str;
+    {
+        let sum = sum.clone();
+        sum_str = computed(move || format!("Sum: {}", sum.get()));
+    }
+    let loud;
+    {
+        let sum_str = sum_str.clone();
+        loud = computed(move || sum_str.get().to_uppercase());
+    }
+    assert_that(&loud.get()).is_equal_to(String::from("SUM: 3"));
+
+    a.set(10);
+    assert_that(&loud.get()).is_equal_to(String::from("SUM: 12"));
+    b.set(20);
+    assert_that(&loud.get()).is_equal_to(String::from("SUM: 30"));
+
+    let bound = Property::bound_to(loud);
+    a.set(0);
+    assert_that(bound.get()).is_equal_to(String::from("SUM: 20"));
+}
+
+#[test]
+fn computed_fires_once_through_a_diamond() {
+    let mut a = Property::new(1);
+    let doubled;
+    {
+        let a = (&a).into_expr();
+        doubled = computed(move || a.get() * 2);
+    }
+    let described;
+    {
+        let doubled = doubled.clone();
+        described = computed(move || format!("doubled is {}", doubled.get()));
+    }
+    let both = expr::binary(&a, described.clone(), |a, described| format!("a is {}, {}", a, described));
+    assert_that(&described.height()).is_equal_to(&2);
+    assert_that(&both.height()).is_equal_to(&3);
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut l = Listeners::new();
+    {
+        let (both, seen) = (both.clone(), seen.clone());
+        l.listen_to(both.clone()).with(move || seen.borrow_mut().push(both.get()));
+    }
+    a.set(2);
+    a.set(3);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![
+        String::from("a is 2, doubled is 4"),
+        String::from("a is 3, doubled is 6"),
+    ]);
+}
+
+#[test]
+fn computed_forwards_disposal_of_what_it_read() {
+    let use_a = Property::new(true);
+    let a = Property::new(1);
+    let b = Property::new(2);
+    let c = Property::new(3);
+    let inner;
+    {
+        let c = (&c).into_expr();
+        inner = computed(move || c.get() * 10);
+    }
+    let result;
+    {
+        let (use_a, a, b, inner) = ((&use_a).into_expr(), (&a).into_expr(), (&b).into_expr(), inner.clone());
+        result = computed(move || if use_a.get() { a.get() + inner.get() } else { b.get() });
+    }
+
+    let disposed = Rc::new(Cell::new(0));
+    let handler;
+    {
+        let disposed = disposed.clone();
+        handler = DisposalHandler::new(move || disposed.set(disposed.get() + 1));
+    }
+    result.add_disposal_handler(&handler);
+    assert_that(&result.get()).is_equal_to(&31);
+
+    // Not read on the last run
+    drop(b);
+    assert_that(&disposed.get()).is_equal_to(&0);
+
+    // Read through another computed expression
+    drop(c);
+    assert_that(&disposed.get()).is_equal_to(&1);
+
+    drop(a);
+    assert_that(&disposed.get()).is_equal_to(&2);
+}
+
+#[test]
+fn autorun_reruns_until_dropped() {
+    let mut a = Property::new(1);
+    let mut b = Property::new(2);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let run;
+    {
+        let (a, b) = ((&a).into_expr(), (&b).into_expr());
+        let seen = seen.clone();
+        run = autorun(move || seen.borrow_mut().push(a.get() + b.get()));
+    }
+    assert_that(&*seen.borrow()).is_equal_to(&vec![3]);
+
+    a.set(10);
+    b.set(20);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![3, 12, 30]);
+
+    batch(|| {
+        a.set(100);
+        b.set(200);
+    });
+    assert_that(&*seen.borrow()).is_equal_to(&vec![3, 12, 30, 300]);
+
+    drop(run);
+    a.set(0);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![3, 12, 30, 300]);
+}
+
+#[test]
+fn autorun_follows_conditional_reads() {
+    let mut flag = Property::new(false);
+    let mut x = Property::new(1);
+    let runs = Rc::new(Cell::new(0));
+    let _run;
+    {
+        let (flag, x) = ((&flag).into_expr(), (&x).into_expr());
+        let runs = runs.clone();
+        _run = autorun(move || {
+            runs.set(runs.get() + 1);
+            if flag.get() {
+                x.get();
+            }
+        });
+    }
+    assert_that(&runs.get()).is_equal_to(&1);
+
+    x.set(2);
+    assert_that(&runs.get()).is_equal_to(&1);
+
+    flag.set(true);
+    assert_that(&runs.get()).is_equal_to(&2);
+    x.set(3);
+    assert_that(&runs.get()).is_equal_to(&3);
+}
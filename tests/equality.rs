@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate spectral;
+extern crate pebl;
+
+use std::cell::Cell;
+use std::f32;
+use std::rc::Rc;
+use spectral::prelude::*;
+use pebl::equality::{AlwaysNotify, Approx, PtrEq};
+use pebl::obsv::{InvalidationHandler, Observable};
+use pebl::prelude::*;
+
+fn counting_handler() -> (InvalidationHandler, Rc<Cell<i32>>) {
+    let count = Rc::new(Cell::new(0));
+    let count_clone = count.clone();
+    (InvalidationHandler::new(move || count_clone.set(count_clone.get() + 1)), count)
+}
+
+#[test]
+fn nan_fires_on_every_set_by_default() {
+    let mut o = Observable::new(f32::NAN);
+    let (handler, count) = counting_handler();
+    o.add_invalidation_handler(&handler);
+
+    o.set(f32::NAN);
+    o.set(f32::NAN);
+    assert_that(&count.get()).is_equal_to(&2);
+}
+
+#[test]
+fn approx_policy_ignores_small_changes_and_repeated_nan() {
+    let mut o = Observable::with_policy(1.0f32, Approx(0.001));
+    let (handler, count) = counting_handler();
+    o.add_invalidation_handler(&handler);
+
+    o.set(1.0005);
+    assert_that(&count.get()).is_equal_to(&0);
+    assert_that(o.get()).is_equal_to(&1.0);
+
+    o.set(f32::NAN);
+    o.set(f32::NAN);
+    assert_that(&count.get()).is_equal_to(&1);
+
+    o.set(2.0);
+    assert_that(&count.get()).is_equal_to(&2);
+}
+
+#[test]
+fn always_notify_policy_fires_on_same_value() {
+    let mut o = Observable::with_policy(10, AlwaysNotify);
+    let (handler, count) = counting_handler();
+    o.add_invalidation_handler(&handler);
+
+    o.set(10);
+    o.set(10);
+    assert_that(&count.get()).is_equal_to(&2);
+}
+
+#[test]
+fn ptr_eq_policy_compares_allocations() {
+    let shared = Rc::new(vec![1, 2, 3]);
+    let mut o = Observable::with_policy(shared.clone(), PtrEq);
+    let (handler, count) = counting_handler();
+    o.add_invalidation_handler(&handler);
+
+    o.set(shared.clone());
+    assert_that(&count.get()).is_equal_to(&0);
+
+    o.set(Rc::new(vec![1, 2, 3]));
+    assert_that(&count.get()).is_equal_to(&1);
+}
+
+#[test]
+fn closure_can_be_used_as_policy() {
+    let mut o = Observable::with_policy(String::from("Hello"), |old: &String, new: &String| old.to_lowercase() == new.to_lowercase());
+    let (handler, count) = counting_handler();
+    o.add_invalidation_handler(&handler);
+
+    o.set(String::from("HELLO"));
+    assert_that(&count.get()).is_equal_to(&0);
+
+    o.set(String::from("World"));
+    assert_that(&count.get()).is_equal_to(&1);
+}
+
+#[test]
+fn properties_work_with_types_without_partial_eq() {
+    #[derive(Clone)]
+    struct Point { x: i32, y: i32 }
+
+    let mut p = Property::with_policy(Point { x: 1, y: 2 }, AlwaysNotify);
+    let p_dest = Property::bound_to(expr::unary(&p, |pt| pt.x + pt.y));
+    let count = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let count = count.clone();
+        l.listen_to(&p).with(move || count.set(count.get() + 1));
+    }
+
+    assert_that(p_dest.get()).is_equal_to(&3);
+    p.set(Point { x: 10, y: 20 });
+    assert_that(p_dest.get()).is_equal_to(&30);
+    assert_that(&count.get()).is_equal_to(&1);
+}
@@ -1,5 +1,6 @@
 #[macro_use]
 extern crate spectral;
+#[macro_use]
 extern crate pebl;
 
 use spectral::prelude::*;
@@ -98,6 +99,62 @@ fn binary_expression_works() {
     assert_that(&e.get()).is_equal_to(&String::from("Goodbye, World"));
 }
 
+#[test]
+fn change_handler_can_listen_to_expression() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut p1 = Property::new(1);
+    let mut p2 = Property::new(2);
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let changes = changes.clone();
+        handler = ChangeHandler::new(move |old: &i32, new: &i32| changes.borrow_mut().push((*old, *new)));
+    }
+    p1.plus(&p2).add_change_handler(&handler);
+
+    p1.set(10);
+    p2.set(20);
+    assert_that(&*changes.borrow()).is_equal_to(&vec![(3, 12), (12, 30)]);
+
+    // Changes which cancel out don't report anything
+    batch(|| {
+        p1.set(11);
+        p2.set(19);
+    });
+    assert_that(&changes.borrow().len()).is_equal_to(&2);
+
+    drop(handler);
+    p1.set(0);
+    assert_that(&changes.borrow().len()).is_equal_to(&2);
+}
+
+#[test]
+fn expressions_forward_disposal_of_their_sources() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let p1 = Property::new(1);
+    let mut p2 = Property::new(2);
+    let e = p1.plus(&p2);
+
+    let disposed = Rc::new(Cell::new(false));
+    let handler;
+    {
+        let disposed = disposed.clone();
+        handler = DisposalHandler::new(move || disposed.set(true));
+    }
+    e.add_disposal_handler(&handler);
+
+    p2.set(3);
+    assert_that(&disposed.get()).is_false();
+
+    drop(p1);
+    assert_that(&disposed.get()).is_true();
+    assert_that(&e.try_get()).is_none();
+}
+
 #[test]
 fn can_clone_expr() {
     let mut p1 = Property::new(1);
@@ -118,6 +175,307 @@ fn can_clone_expr() {
     assert_that(p5.get()).is_equal_to(&1110);
 }
 
+#[test]
+fn derived_expressions_cache_their_value() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let mut p1 = Property::new(1);
+    let p2 = Property::new(10);
+    let evaluations = Rc::new(Cell::new(0));
+    let sum;
+    {
+        let evaluations = evaluations.clone();
+        sum = expr::binary(&p1, &p2, move |a, b| {
+            evaluations.set(evaluations.get() + 1);
+            a + b
+        });
+    }
+    let doubled = expr::unary(sum.clone(), |val| val * 2);
+
+    assert_that(&doubled.get()).is_equal_to(22);
+    assert_that(&doubled.get()).is_equal_to(22);
+    assert_that(&sum.get()).is_equal_to(11);
+    assert_that(&evaluations.get()).is_equal_to(1);
+
+    p1.set(2);
+    assert_that(&evaluations.get()).is_equal_to(1); // Recomputed lazily
+    assert_that(&doubled.get()).is_equal_to(24);
+    assert_that(&sum.get()).is_equal_to(12);
+    assert_that(&evaluations.get()).is_equal_to(2);
+}
+
+#[test]
+fn derived_expressions_are_fresh_inside_a_batch() {
+    let mut p = Property::new(1);
+    let doubled = expr::unary(&p, |val| val * 2);
+    assert_that(&doubled.get()).is_equal_to(2);
+
+    batch(|| {
+        p.set(2);
+        assert_that(&doubled.get()).is_equal_to(4);
+        p.set(3);
+        assert_that(&doubled.get()).is_equal_to(6);
+    });
+    assert_that(&doubled.get()).is_equal_to(6);
+}
+
+#[test]
+fn derived_expressions_are_cached_for_reads_inside_a_listener() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let mut p = Property::new(1);
+    let evaluations = Rc::new(Cell::new(0));
+    let doubled;
+    {
+        let evaluations = evaluations.clone();
+        doubled = expr::unary(&p, move |val| {
+            evaluations.set(evaluations.get() + 1);
+            val * 2
+        });
+    }
+    let seen = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let (doubled, seen) = (doubled.clone(), seen.clone());
+        l.listen_to(doubled.clone()).with(move || {
+            for _ in 0..3 {
+                seen.set(doubled.get());
+            }
+        });
+    }
+    assert_that(&doubled.get()).is_equal_to(2);
+    assert_that(&evaluations.get()).is_equal_to(1);
+
+    p.set(2);
+    assert_that(&seen.get()).is_equal_to(4);
+    assert_that(&evaluations.get()).is_equal_to(2);
+
+    batch(|| p.set(3));
+    assert_that(&seen.get()).is_equal_to(6);
+    assert_that(&doubled.get()).is_equal_to(6);
+    assert_that(&evaluations.get()).is_equal_to(3);
+}
+
+#[test]
+fn nary_expressions_combine_many_sources() {
+    let mut p1 = Property::new(1);
+    let p2 = Property::new(2);
+    let p3 = Property::new(3);
+    let p4 = Property::new(4);
+    let p5 = Property::new(5);
+    let p6 = Property::new(6);
+    let p7 = Property::new(7);
+    let mut p8 = Property::new(String::from("sum"));
+
+    let sum3 = expr::map3(&p1, &p2, &p3, |a, b, c| a + b + c);
+    let sum4 = expr::map4(&p1, &p2, &p3, &p4, |a, b, c, d| a + b + c + d);
+    let sum8 = expr::map8(&p1, &p2, &p3, &p4, &p5, &p6, &p7, &p8,
+                          |a, b, c, d, e, f, g, label| format!("{}: {}", label, a + b + c + d + e + f + g));
+    assert_that(&sum3.get()).is_equal_to(6);
+    assert_that(&sum4.get()).is_equal_to(10);
+    assert_that(&sum8.get()).is_equal_to(String::from("sum: 28"));
+
+    p1.set(11);
+    p8.set(String::from("total"));
+    assert_that(&sum3.get()).is_equal_to(16);
+    assert_that(&sum4.get()).is_equal_to(20);
+    assert_that(&sum8.get()).is_equal_to(String::from("total: 38"));
+}
+
+#[test]
+fn nary_expressions_notify_once_and_track_disposal() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let mut p1 = Property::new(1);
+    let p2 = Property::new(2);
+    let p3 = Property::new(3);
+    let product = expr::map3(&p1, &p2, &p1, |a, b, c| a * b * c);
+    let count = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let count = count.clone();
+        l.listen_to(product.clone()).with(move || count.set(count.get() + 1));
+    }
+
+    p1.set(3);
+    assert_that(&count.get()).is_equal_to(1);
+    assert_that(&product.get()).is_equal_to(18);
+
+    let with_p3 = expr::map3(&p1, &p2, &p3, |a, b, c| a + b + c);
+    drop(p3);
+    assert_that(&with_p3.try_get()).is_none();
+}
+
+#[test]
+fn combine_vec_collects_any_number_of_sources() {
+    use std::rc::Rc;
+    use pebl::expr::IntoExpression;
+
+    let mut items: Vec<Property<i32>> = (0..5).map(Property::new).collect();
+    let all = expr::combine_vec(items.iter().map(|item| item.into_expr()).collect());
+    assert_that(&all.get()).is_equal_to(vec![0, 1, 2, 3, 4]);
+
+    items[4].set(40);
+    assert_that(&all.get()).is_equal_to(vec![0, 1, 2, 3, 40]);
+
+    let empty = expr::combine_vec(Vec::<Rc<Expression<i32>>>::new());
+    assert_that(&empty.get()).is_equal_to(Vec::new());
+
+    items.pop();
+    assert_that(&all.try_get()).is_none();
+}
+
+#[test]
+fn literals_and_constants_can_be_used_as_expressions() {
+    use pebl::expr::Const;
+
+    let mut p_int = Property::new(10);
+    let mut p_bool = Property::new(true);
+    let mut p_str = Property::new(String::from("Hello"));
+
+    let math = p_int.plus(5).times(2);
+    let cmp = p_int.gt(10);
+    let logic = p_bool.and(true).or(false);
+    let text = expr::when(&p_bool, &p_str, "Goodbye");
+    let constant = expr::binary(&p_int, expr::constant(vec![1, 2, 3]), |i, v| v.iter().sum::<i32>() + i);
+    let wrapped = Const(100).plus(&p_int);
+
+    assert_that(&math.get()).is_equal_to(30);
+    assert_that(&cmp.get()).is_false();
+    assert_that(&logic.get()).is_true();
+    assert_that(&text.get()).is_equal_to(String::from("Hello"));
+    assert_that(&constant.get()).is_equal_to(16);
+    assert_that(&wrapped.get()).is_equal_to(110);
+
+    p_int.set(20);
+    p_bool.set(false);
+    p_str.set(String::from("Hi"));
+
+    assert_that(&math.get()).is_equal_to(50);
+    assert_that(&cmp.get()).is_true();
+    assert_that(&logic.get()).is_false();
+    assert_that(&text.get()).is_equal_to(String::from("Goodbye"));
+    assert_that(&constant.get()).is_equal_to(26);
+    assert_that(&wrapped.get()).is_equal_to(120);
+    assert_that(&expr::text::trim("  padded  ").get()).is_equal_to(String::from("padded"));
+}
+
+#[test]
+fn switch_map_follows_the_current_inner_expression() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use pebl::expr::IntoExpression;
+
+    let mut titles = vec![Property::new(String::from("A")), Property::new(String::from("B"))];
+    let title_exprs: Vec<Rc<Expression<String>>> = titles.iter().map(|title| title.into_expr()).collect();
+    let mut selected = Property::new(0);
+    let selected_title = selected.switch_map(move |&i| title_exprs[i].clone());
+
+    let count = Rc::new(Cell::new(0));
+    let count_clone = count.clone();
+    let handler = InvalidationHandler::new(move || count_clone.set(count_clone.get() + 1));
+    selected_title.add_invalidation_handler(&handler);
+    assert_that(&selected_title.get()).is_equal_to(String::from("A"));
+
+    titles[0].set(String::from("A2"));
+    assert_that(&count.get()).is_equal_to(1);
+    assert_that(&selected_title.get()).is_equal_to(String::from("A2"));
+
+    selected.set(1);
+    assert_that(&count.get()).is_equal_to(2);
+    assert_that(&selected_title.get()).is_equal_to(String::from("B"));
+
+    // The old inner expression no longer affects us
+    titles[0].set(String::from("A3"));
+    assert_that(&count.get()).is_equal_to(2);
+
+    titles[1].set(String::from("B2"));
+    assert_that(&count.get()).is_equal_to(3);
+    assert_that(&selected_title.get()).is_equal_to(String::from("B2"));
+}
+
+#[test]
+fn switch_map_is_consistent_inside_a_batch() {
+    use std::rc::Rc;
+    use pebl::expr::IntoExpression;
+
+    let mut prices = vec![Property::new(10), Property::new(20)];
+    let price_exprs: Vec<Rc<Expression<i32>>> = prices.iter().map(|price| price.into_expr()).collect();
+    let mut selected = Property::new(0);
+    let selected_price = expr::switch_map(&selected, move |&i| price_exprs[i].clone());
+    let mut total = Property::bound_to(selected_price.clone().times(2));
+
+    batch(|| {
+        selected.set(1);
+        assert_that(&selected_price.get()).is_equal_to(20);
+        prices[1].set(25);
+    });
+    assert_that(total.get()).is_equal_to(&50);
+
+    prices[0].set(15);
+    assert_that(total.get()).is_equal_to(&50);
+}
+
+#[test]
+fn switch_map_forwards_disposal_of_the_current_inner_expression() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use pebl::expr::IntoExpression;
+
+    let first = Property::new(String::from("A"));
+    let second = Property::new(String::from("B"));
+    let title_exprs: Vec<Rc<Expression<String>>> = vec![(&first).into_expr(), (&second).into_expr()];
+    let mut selected = Property::new(0);
+    let selected_title = selected.switch_map(move |&i| title_exprs[i].clone());
+
+    let disposed = Rc::new(Cell::new(false));
+    let handler;
+    {
+        let disposed = disposed.clone();
+        handler = DisposalHandler::new(move || disposed.set(true));
+    }
+    selected_title.add_disposal_handler(&handler);
+
+    selected.set(1);
+    drop(first);
+    assert_that(&disposed.get()).is_false();
+
+    drop(second);
+    assert_that(&disposed.get()).is_true();
+    assert_that(&selected_title.try_get()).is_none();
+}
+
+#[test]
+fn switch_map_reuses_the_inner_expression_while_the_outer_is_unchanged() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use pebl::expr::IntoExpression;
+
+    let mut prices = vec![Property::new(10), Property::new(20)];
+    let price_exprs: Vec<Rc<Expression<i32>>> = prices.iter().map(|price| price.into_expr()).collect();
+    let selected = Property::new(1);
+    let switches = Rc::new(Cell::new(0));
+    let selected_price;
+    {
+        let switches = switches.clone();
+        selected_price = expr::switch_map(&selected, move |&i| {
+            switches.set(switches.get() + 1);
+            price_exprs[i].clone()
+        });
+    }
+    assert_that(&switches.get()).is_equal_to(1);
+
+    batch(|| {
+        prices[1].set(25);
+        assert_that(&selected_price.get()).is_equal_to(25);
+        assert_that(&selected_price.get()).is_equal_to(25);
+    });
+    assert_that(&switches.get()).is_equal_to(1);
+}
+
 mod logic {
     use spectral::prelude::*;
     use pebl::prelude::*;
@@ -168,6 +526,172 @@ mod logic {
         p.set(false);
         assert_that(&e.get()).is_equal_to(&true);
     }
+
+    #[test]
+    fn when_expr_picks_a_branch() {
+        let mut cond = Property::new(true);
+        let mut p_true = Property::new(1);
+        let mut p_false = Property::new(2);
+        let e = expr::when(&cond, &p_true, &p_false);
+        let e_chained = cond.inverted().then_else(&p_true, &p_false);
+
+        assert_that(&e.get()).is_equal_to(&1);
+        assert_that(&e_chained.get()).is_equal_to(&2);
+
+        cond.set(false);
+        assert_that(&e.get()).is_equal_to(&2);
+        assert_that(&e_chained.get()).is_equal_to(&1);
+
+        p_true.set(10);
+        p_false.set(20);
+        assert_that(&e.get()).is_equal_to(&20);
+        assert_that(&e_chained.get()).is_equal_to(&10);
+    }
+
+    #[test]
+    fn when_expr_only_evaluates_chosen_branch() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut cond = Property::new(true);
+        let p = Property::new(1);
+        let evaluations = Rc::new(Cell::new(0));
+        let expensive;
+        {
+            let evaluations = evaluations.clone();
+            expensive = expr::unary(&p, move |val| {
+                evaluations.set(evaluations.get() + 1);
+                val * 100
+            });
+        }
+        let e = cond.then_else(&p, expensive);
+
+        assert_that(&e.get()).is_equal_to(&1);
+        assert_that(&evaluations.get()).is_equal_to(&0);
+
+        cond.set(false);
+        assert_that(&e.get()).is_equal_to(&100);
+        assert_that(&evaluations.get()).is_equal_to(&1);
+    }
+
+    #[test]
+    fn when_expr_forwards_invalidation_from_all_sources() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut cond = Property::new(true);
+        let mut p_true = Property::new(1);
+        let mut p_false = Property::new(2);
+        let e = expr::when(&cond, &p_true, &p_false);
+        let count = Rc::new(Cell::new(0));
+        let mut l = Listeners::new();
+        {
+            let count = count.clone();
+            l.listen_to(e).with(move || count.set(count.get() + 1));
+        }
+
+        cond.set(false);
+        p_true.set(10);
+        p_false.set(20);
+        assert_that(&count.get()).is_equal_to(&3);
+    }
+
+    #[test]
+    fn when_expr_picks_between_literals() {
+        let mut cond = Property::new(false);
+        let e = expr::when(&cond, "yes", "no");
+        let e_chained = cond.inverted().then_else(1, 0);
+
+        assert_that(&e.get()).is_equal_to(&String::from("no"));
+        assert_that(&e_chained.get()).is_equal_to(&1);
+
+        cond.set(true);
+        assert_that(&e.get()).is_equal_to(&String::from("yes"));
+        assert_that(&e_chained.get()).is_equal_to(&0);
+    }
+
+    #[test]
+    fn xor_nand_and_implies_exprs_work() {
+        let mut p1 = Property::new(false);
+        let mut p2 = Property::new(false);
+
+        let xor = p1.xor(&p2);
+        let nand = expr::logic::nand(&p1, &p2);
+        let implies = p1.implies(&p2);
+
+        let mut results = Vec::new();
+        for &(val1, val2) in &[(false, false), (false, true), (true, false), (true, true)] {
+            p1.set(val1);
+            p2.set(val2);
+            results.push((xor.get(), nand.get(), implies.get()));
+        }
+        assert_that(&results).is_equal_to(vec![
+            (false, true, true),
+            (true, true, true),
+            (true, true, false),
+            (false, false, true),
+        ]);
+    }
+
+    #[test]
+    fn flat_logic_exprs_work() {
+        let mut fields: Vec<Property<bool>> = (0..4).map(|_| Property::new(true)).collect();
+
+        let all = expr::logic::all(fields.iter());
+        let any = expr::logic::any(&fields);
+        let none = expr::logic::none(fields.iter());
+        let count = expr::logic::count_true(&fields);
+
+        assert_that(&all.get()).is_true();
+        assert_that(&any.get()).is_true();
+        assert_that(&none.get()).is_false();
+        assert_that(&count.get()).is_equal_to(&4);
+
+        fields[2].set(false);
+        assert_that(&all.get()).is_false();
+        assert_that(&any.get()).is_true();
+        assert_that(&count.get()).is_equal_to(&3);
+
+        for field in &mut fields {
+            field.set(false);
+        }
+        assert_that(&any.get()).is_false();
+        assert_that(&none.get()).is_true();
+        assert_that(&count.get()).is_equal_to(&0);
+
+        let empty: Vec<Property<bool>> = Vec::new();
+        assert_that(&expr::logic::all(&empty).get()).is_true();
+        assert_that(&expr::logic::any(&empty).get()).is_false();
+    }
+
+    #[test]
+    fn flat_logic_exprs_stop_early() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use pebl::expr::IntoExpression;
+
+        let mut first = Property::new(false);
+        let second = Property::new(true);
+        let evaluations = Rc::new(Cell::new(0));
+        let counted;
+        {
+            let evaluations = evaluations.clone();
+            counted = expr::unary(&second, move |&val| {
+                evaluations.set(evaluations.get() + 1);
+                val
+            });
+        }
+        let all = expr::logic::all(vec![(&first).into_expr(), counted.clone()]);
+        let any = expr::logic::any(vec![first.inverted(), counted]);
+
+        assert_that(&all.get()).is_false();
+        assert_that(&any.get()).is_true();
+        assert_that(&evaluations.get()).is_equal_to(&0);
+
+        first.set(true);
+        assert_that(&all.get()).is_true();
+        assert_that(&evaluations.get()).is_equal_to(&1);
+    }
 }
 
 mod math {
@@ -260,6 +784,258 @@ mod math {
         p1.set(0);
         assert_that(&e.get()).is_equal_to(&0);
     }
+
+    #[test]
+    fn minus_div_and_rem_exprs_work()
+    {
+        let mut p1 = Property::new(17);
+        let p2 = Property::new(5);
+
+        let minus = expr::math::minus(&p1, &p2);
+        let div = p1.divided_by(&p2);
+        let rem = p1.rem(&p2);
+
+        assert_that(&minus.get()).is_equal_to(&12);
+        assert_that(&div.get()).is_equal_to(&3);
+        assert_that(&rem.get()).is_equal_to(&2);
+
+        p1.set(-3);
+        assert_that(&minus.get()).is_equal_to(&-8);
+        assert_that(&div.get()).is_equal_to(&0);
+        assert_that(&rem.get()).is_equal_to(&-3);
+    }
+
+    #[test]
+    fn checked_exprs_are_none_when_dividing_by_zero()
+    {
+        let p1 = Property::new(7);
+        let mut p2 = Property::new(0);
+        let p_flt = Property::new(1.5);
+        let mut p_flt_zero = Property::new(0.0);
+
+        let div = p1.checked_div(&p2);
+        let rem = expr::math::checked_rem(&p1, &p2);
+        let div_flt = p_flt.checked_div(&p_flt_zero);
+
+        assert_that(&div.get()).is_none();
+        assert_that(&rem.get()).is_none();
+        assert_that(&div_flt.get()).is_none();
+
+        p2.set(2);
+        p_flt_zero.set(0.5);
+        assert_that(&div.get()).is_equal_to(Some(3));
+        assert_that(&rem.get()).is_equal_to(Some(1));
+        assert_that(&div_flt.get()).is_equal_to(Some(3.0));
+    }
+
+    #[test]
+    fn min_max_and_clamp_exprs_work()
+    {
+        let mut p = Property::new(5);
+        let mut p_max = Property::new(10);
+
+        let min = p.min(&p_max);
+        let max = expr::math::max(&p, &p_max);
+        let clamped = p.clamp(0, &p_max);
+
+        assert_that(&min.get()).is_equal_to(&5);
+        assert_that(&max.get()).is_equal_to(&10);
+        assert_that(&clamped.get()).is_equal_to(&5);
+
+        p.set(-1);
+        assert_that(&clamped.get()).is_equal_to(&0);
+
+        p.set(20);
+        assert_that(&min.get()).is_equal_to(&10);
+        assert_that(&max.get()).is_equal_to(&20);
+        assert_that(&clamped.get()).is_equal_to(&10);
+
+        p_max.set(15);
+        assert_that(&clamped.get()).is_equal_to(&15);
+    }
+
+    #[test]
+    fn pow_expr_works_with_int_and_float()
+    {
+        let mut p_int = Property::new(3);
+        let mut p_flt = Property::new(4.0);
+
+        let squared = p_int.pow(2);
+        let cubed = expr::math::pow(&p_flt, 3);
+        let root = p_flt.powf(0.5);
+
+        assert_that(&squared.get()).is_equal_to(&9);
+        assert_that(&cubed.get()).is_equal_to(&64.0);
+        assert_that(&root.get()).is_equal_to(&2.0);
+
+        p_int.set(-4);
+        p_flt.set(9.0);
+        assert_that(&squared.get()).is_equal_to(&16);
+        assert_that(&cubed.get()).is_equal_to(&729.0);
+        assert_that(&root.get()).is_equal_to(&3.0);
+    }
+
+    #[test]
+    fn float_exprs_work()
+    {
+        let mut p = Property::new(2.25f32);
+
+        let sqrt = p.sqrt();
+        let floor = p.floor();
+        let ceil = expr::math::ceil(&p);
+        let round = p.round();
+        let lerp = expr::math::lerp(10.0, 20.0, p.minus(2.0));
+
+        assert_that(&sqrt.get()).is_equal_to(&1.5);
+        assert_that(&floor.get()).is_equal_to(&2.0);
+        assert_that(&ceil.get()).is_equal_to(&3.0);
+        assert_that(&round.get()).is_equal_to(&2.0);
+        assert_that(&lerp.get()).is_equal_to(&12.5);
+
+        p.set(2.75);
+        assert_that(&floor.get()).is_equal_to(&2.0);
+        assert_that(&round.get()).is_equal_to(&3.0);
+        assert_that(&lerp.get()).is_equal_to(&17.5);
+    }
+
+    #[test]
+    fn math_exprs_work_with_non_copy_types()
+    {
+        use std::ops::{Add, Mul, Neg};
+
+        // Stands in for an arbitrary precision number, which can't be Copy
+        #[derive(Clone, Debug, Default, PartialEq, PartialOrd)]
+        struct BigNum(Box<i64>);
+
+        impl Add for BigNum {
+            type Output = BigNum;
+            fn add(self, rhs: BigNum) -> BigNum { BigNum(Box::new(*self.0 + *rhs.0)) }
+        }
+
+        impl Mul for BigNum {
+            type Output = BigNum;
+            fn mul(self, rhs: BigNum) -> BigNum { BigNum(Box::new(*self.0 * *rhs.0)) }
+        }
+
+        impl Neg for BigNum {
+            type Output = BigNum;
+            fn neg(self) -> BigNum { BigNum(Box::new(-*self.0)) }
+        }
+
+        let mut big_a = Property::new(BigNum(Box::new(2)));
+        let big_b = Property::new(BigNum(Box::new(3)));
+
+        let sum = big_a.plus(&big_b);
+        let product = &big_a * &big_b;
+        let abs = expr::math::abs(&big_a);
+        let max = big_a.max(&big_b);
+
+        assert_that(&sum.get()).is_equal_to(BigNum(Box::new(5)));
+        assert_that(&product.get()).is_equal_to(BigNum(Box::new(6)));
+        assert_that(&abs.get()).is_equal_to(BigNum(Box::new(2)));
+        assert_that(&max.get()).is_equal_to(BigNum(Box::new(3)));
+
+        big_a.set(BigNum(Box::new(-7)));
+        assert_that(&sum.get()).is_equal_to(BigNum(Box::new(-4)));
+        assert_that(&product.get()).is_equal_to(BigNum(Box::new(-21)));
+        assert_that(&abs.get()).is_equal_to(BigNum(Box::new(7)));
+        assert_that(&max.get()).is_equal_to(BigNum(Box::new(3)));
+    }
+}
+
+mod ops {
+    use spectral::prelude::*;
+    use pebl::prelude::*;
+
+    #[test]
+    fn arithmetic_operators_build_expressions() {
+        let mut price = Property::new(10);
+        let mut qty = Property::new(3);
+        let tax = Property::new(2);
+
+        let total = &price * &qty + &tax;
+        let per_item = &total / &qty;
+        let refund = -(total.clone() - 2);
+        let doubled: Expr<i32> = 2 * &total;
+
+        assert_that(&total.get()).is_equal_to(&32);
+        assert_that(&per_item.get()).is_equal_to(&10);
+        assert_that(&refund.get()).is_equal_to(&-30);
+        assert_that(&doubled.get()).is_equal_to(&64);
+
+        price.set(20);
+        qty.set(1);
+        assert_that(&total.get()).is_equal_to(&22);
+        assert_that(&per_item.get()).is_equal_to(&22);
+        assert_that(&refund.get()).is_equal_to(&-20);
+        assert_that(&doubled.get()).is_equal_to(&44);
+    }
+
+    #[test]
+    fn logical_operators_build_expressions() {
+        let mut enabled = Property::new(true);
+        let mut busy = Property::new(false);
+
+        let clickable = &enabled & !&busy;
+        let blocked = !&enabled | &busy;
+
+        assert_that(&clickable.get()).is_true();
+        assert_that(&blocked.get()).is_false();
+
+        busy.set(true);
+        assert_that(&clickable.get()).is_false();
+        assert_that(&blocked.get()).is_true();
+
+        busy.set(false);
+        enabled.set(false);
+        assert_that(&clickable.get()).is_false();
+        assert_that(&blocked.get()).is_true();
+    }
+
+    #[test]
+    fn operator_exprs_work_with_other_expressions() {
+        let mut width = Property::new(2.0);
+        let area = Expr::new(width.times(&width)) * 0.5;
+        let label = area.clone().gt(4.0).then_else("big", "small");
+        let half_area = Property::bound_to(&area / 2.0);
+
+        assert_that(&label.get()).is_equal_to(&String::from("small"));
+        assert_that(half_area.get()).is_equal_to(&1.0);
+
+        width.set(4.0);
+        assert_that(&label.get()).is_equal_to(&String::from("big"));
+        assert_that(half_area.get()).is_equal_to(&4.0);
+    }
+
+    #[test]
+    fn expression_methods_are_unambiguous_with_std_ops_in_scope() {
+        use std::ops::*;
+
+        let mut total = Property::new(12);
+        let mut enabled = Property::new(true);
+        let total_expr = Expr::new(&total);
+        let enabled_expr = Expr::new(&enabled);
+
+        let halved = total_expr.clone().divided_by(2);
+        let negated = total_expr.clone().negated();
+        let disabled = enabled_expr.clone().inverted();
+        let halved_op = total_expr.clone().div(2);
+        let negated_op = total_expr.neg();
+        let disabled_op = enabled_expr.not();
+
+        assert_that(&halved.get()).is_equal_to(&6);
+        assert_that(&negated.get()).is_equal_to(&-12);
+        assert_that(&disabled.get()).is_false();
+        assert_that(&halved_op.get()).is_equal_to(&6);
+        assert_that(&negated_op.get()).is_equal_to(&-12);
+        assert_that(&disabled_op.get()).is_false();
+
+        total.set(20);
+        enabled.set(false);
+        assert_that(&halved.get()).is_equal_to(&10);
+        assert_that(&negated_op.get()).is_equal_to(&-20);
+        assert_that(&disabled_op.get()).is_true();
+    }
 }
 
 mod cmp {
@@ -476,6 +1252,138 @@ mod cmp {
         p.set(2);
         assert_that(&e.get()).is_true();
     }
+
+    #[test]
+    fn cmp_exprs_work_with_non_copy_types()
+    {
+        let mut name = Property::new(String::from("guest"));
+        let mut tags = Property::new(vec![1, 2]);
+        let other_tags = Property::new(vec![1, 3]);
+
+        let is_admin = name.eq_val(String::from("admin"));
+        let sorted_first = expr::cmp::lt(&tags, &other_tags);
+
+        assert_that(&is_admin.get()).is_false();
+        assert_that(&sorted_first.get()).is_true();
+
+        name.set(String::from("admin"));
+        tags.set(vec![2]);
+        assert_that(&is_admin.get()).is_true();
+        assert_that(&sorted_first.get()).is_false();
+    }
+}
+
+mod option {
+    use spectral::prelude::*;
+    use pebl::prelude::*;
+
+    #[test]
+    fn is_some_and_is_none_exprs_work() {
+        let mut p = Property::new(None);
+
+        let is_some = p.is_some();
+        let is_none = expr::option::is_none(&p);
+        assert_that(&is_some.get()).is_false();
+        assert_that(&is_none.get()).is_true();
+
+        p.set(Some(1));
+        assert_that(&is_some.get()).is_true();
+        assert_that(&is_none.get()).is_false();
+    }
+
+    #[test]
+    fn unwrap_exprs_work() {
+        let mut p = Property::new(Some(1));
+        let mut fallback = Property::new(10);
+
+        let unwrapped = p.unwrap_or(-1);
+        let unwrapped_expr = p.unwrap_or_expr(&fallback);
+        assert_that(&unwrapped.get()).is_equal_to(&1);
+        assert_that(&unwrapped_expr.get()).is_equal_to(&1);
+
+        p.set(None);
+        assert_that(&unwrapped.get()).is_equal_to(&-1);
+        assert_that(&unwrapped_expr.get()).is_equal_to(&10);
+
+        fallback.set(20);
+        assert_that(&unwrapped_expr.get()).is_equal_to(&20);
+    }
+
+    #[test]
+    fn map_some_and_and_then_exprs_work() {
+        let mut p = Property::new(Some(String::from("42")));
+
+        let len = p.map_some(|val| val.len());
+        let parsed = expr::option::and_then(&p, |val| val.parse::<i32>().ok());
+        assert_that(&len.get()).is_equal_to(Some(2));
+        assert_that(&parsed.get()).is_equal_to(Some(42));
+
+        p.set(Some(String::from("abc")));
+        assert_that(&len.get()).is_equal_to(Some(3));
+        assert_that(&parsed.get()).is_none();
+
+        p.set(None);
+        assert_that(&len.get()).is_none();
+        assert_that(&parsed.get()).is_none();
+    }
+
+    #[test]
+    fn option_exprs_forward_invalidation() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut p = Property::new(None);
+        let e = p.map_some(|val: &i32| val * 2).unwrap_or(0);
+        let count = Rc::new(Cell::new(0));
+        let mut l = Listeners::new();
+        {
+            let count = count.clone();
+            l.listen_to(e.clone()).with(move || count.set(count.get() + 1));
+        }
+
+        p.set(Some(5));
+        assert_that(&count.get()).is_equal_to(&1);
+        assert_that(&e.get()).is_equal_to(&10);
+    }
+}
+
+mod result {
+    use spectral::prelude::*;
+    use pebl::prelude::*;
+
+    #[test]
+    fn result_exprs_work() {
+        let mut p: Property<Result<i32, String>> = Property::new(Ok(1));
+
+        let ok = p.ok();
+        let is_ok = expr::result::is_ok(&p);
+        let is_err = p.is_err();
+        let err_message = p.err_message();
+        assert_that(&ok.get()).is_equal_to(Some(1));
+        assert_that(&is_ok.get()).is_true();
+        assert_that(&is_err.get()).is_false();
+        assert_that(&err_message.get()).is_none();
+
+        p.set(Err(String::from("out of range")));
+        assert_that(&ok.get()).is_none();
+        assert_that(&is_ok.get()).is_false();
+        assert_that(&is_err.get()).is_true();
+        assert_that(&err_message.get()).is_equal_to(Some(String::from("out of range")));
+    }
+
+    #[test]
+    fn result_exprs_combine_with_parse() {
+        let mut input = Property::new(String::from("7"));
+
+        let value = input.parse::<u32>().ok().unwrap_or(0);
+        let error = expr::result::err_message(input.parse::<u32>());
+        assert_that(&value.get()).is_equal_to(&7);
+        assert_that(&error.get()).is_none();
+
+        input.set(String::from("-7"));
+        assert_that(&value.get()).is_equal_to(&0);
+        assert_that(&error.get()).is_equal_to(Some(String::from("invalid digit found in string")));
+    }
 }
 
 mod text {
@@ -545,4 +1453,140 @@ mod text {
         p.set(String::from("   Hello   "));
         assert_that(&e.get()).is_equal_to(String::from("Hello"));
     }
+
+    #[test]
+    fn char_count_counts_characters_not_bytes() {
+        let mut p = Property::new(String::from("Hello"));
+
+        let e = p.char_count();
+        assert_that(&e.get()).is_equal_to(&5);
+
+        p.set(String::from("Café"));
+        assert_that(&e.get()).is_equal_to(&4);
+        assert_that(&expr::text::len(&p).get()).is_equal_to(&5);
+    }
+
+    #[test]
+    fn concat_and_case_exprs_work() {
+        let mut first = Property::new(String::from("Jane"));
+        let last = Property::new(String::from("Doe"));
+
+        let full = first.concat(" ").concat(&last);
+        let upper = expr::text::to_uppercase(full.clone());
+        let lower = full.clone().to_lowercase();
+
+        assert_that(&full.get()).is_equal_to(String::from("Jane Doe"));
+        assert_that(&upper.get()).is_equal_to(String::from("JANE DOE"));
+        assert_that(&lower.get()).is_equal_to(String::from("jane doe"));
+
+        first.set(String::from("John"));
+        assert_that(&upper.get()).is_equal_to(String::from("JOHN DOE"));
+    }
+
+    #[test]
+    fn search_exprs_work() {
+        let mut p = Property::new(String::from("report.pdf"));
+        let mut pattern = Property::new(String::from("port"));
+
+        let contains = p.contains(&pattern);
+        let starts_with = expr::text::starts_with(&p, "report");
+        let ends_with = p.ends_with(".pdf");
+
+        assert_that(&contains.get()).is_true();
+        assert_that(&starts_with.get()).is_true();
+        assert_that(&ends_with.get()).is_true();
+
+        pattern.set(String::from("draft"));
+        p.set(String::from("summary.txt"));
+        assert_that(&contains.get()).is_false();
+        assert_that(&starts_with.get()).is_false();
+        assert_that(&ends_with.get()).is_false();
+    }
+
+    #[test]
+    fn replace_expr_works() {
+        let mut p = Property::new(String::from("a-b-c"));
+        let mut to = Property::new(String::from("+"));
+
+        let e = p.replace("-", &to);
+        assert_that(&e.get()).is_equal_to(String::from("a+b+c"));
+
+        to.set(String::from(""));
+        assert_that(&e.get()).is_equal_to(String::from("abc"));
+
+        p.set(String::from("x"));
+        assert_that(&e.get()).is_equal_to(String::from("x"));
+    }
+
+    #[test]
+    fn substring_expr_uses_char_range() {
+        let mut p = Property::new(String::from("¡Hola!"));
+        let mut end = Property::new(5);
+
+        let e = p.substring(1, &end);
+        assert_that(&e.get()).is_equal_to(String::from("Hola"));
+
+        end.set(100);
+        assert_that(&e.get()).is_equal_to(String::from("Hola!"));
+
+        end.set(0);
+        assert_that(&e.get()).is_equal_to(String::from(""));
+
+        end.set(3);
+        p.set(String::from(""));
+        assert_that(&e.get()).is_equal_to(String::from(""));
+    }
+
+    #[test]
+    fn split_expr_works() {
+        let mut p = Property::new(String::from("a,b,c"));
+
+        let e = p.split(",");
+        assert_that(&e.get()).is_equal_to(vec![String::from("a"), String::from("b"), String::from("c")]);
+
+        p.set(String::from(""));
+        assert_that(&e.get()).is_equal_to(vec![String::from("")]);
+    }
+
+    #[test]
+    fn format_expr_renders_when_any_argument_changes() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let mut name = Property::new(String::from("Jane"));
+        let mut balance = Property::new(12.5);
+        let e = format_expr!("{}: {:>7.2} ({})", &name, &balance, "USD");
+        let count = Rc::new(Cell::new(0));
+        let mut l = Listeners::new();
+        {
+            let count = count.clone();
+            l.listen_to(e.clone()).with(move || count.set(count.get() + 1));
+        }
+        assert_that(&e.get()).is_equal_to(String::from("Jane:   12.50 (USD)"));
+
+        name.set(String::from("John"));
+        balance.set(-3.0);
+        assert_that(&e.get()).is_equal_to(String::from("John:   -3.00 (USD)"));
+        assert_that(&count.get()).is_equal_to(&2);
+
+        let no_args = format_expr!("{{}}");
+        assert_that(&no_args.get()).is_equal_to(String::from("{}"));
+    }
+
+    #[test]
+    fn parse_expr_works() {
+        let mut p = Property::new(String::from("12"));
+
+        let e = p.parse::<i32>();
+        let e_flt = expr::text::parse::<f64, _>(&p);
+        assert_that(&e.get()).is_equal_to(Ok(12));
+        assert_that(&e_flt.get()).is_equal_to(Ok(12.0));
+
+        p.set(String::from("1.5"));
+        assert_that(&e.get()).is_err();
+        assert_that(&e_flt.get()).is_equal_to(Ok(1.5));
+
+        p.set(String::from(""));
+        assert_that(&e.get()).is_equal_to(Err(String::from("cannot parse integer from empty string")));
+    }
 }
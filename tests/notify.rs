@@ -0,0 +1,418 @@
+#[macro_use]
+extern crate spectral;
+extern crate pebl;
+
+use std::cell::{Cell, RefCell};
+use std::panic;
+use std::rc::Rc;
+use spectral::prelude::*;
+use pebl::prelude::*;
+use pebl::notify::{self, Transaction};
+
+#[test]
+fn batch_fires_each_listener_once() {
+    let mut l = Listeners::new();
+    let mut p_int = Property::<i32>::default();
+    let mut p_str = Property::<String>::default();
+    let mut p_bool = Property::<bool>::default();
+    let listen_count = Rc::new(Cell::new(0));
+    {
+        let listen_count = listen_count.clone();
+        l.listen_to(&p_int).and(&p_str).and(&p_bool).with(move || listen_count.set(listen_count.get() + 1));
+    }
+
+    batch(|| {
+        p_int.set(10);
+        p_str.set(String::from("Ten"));
+        p_bool.set(true);
+        p_int.set(11);
+        assert_that(&listen_count.get()).is_equal_to(&0);
+    });
+    assert_that(&listen_count.get()).is_equal_to(&1);
+}
+
+#[test]
+fn values_are_visible_inside_batch() {
+    let mut p1 = Property::new(1);
+    let mut p2 = Property::new(2);
+    let sum = p1.plus(&p2);
+
+    batch(|| {
+        p1.set(10);
+        assert_that(&sum.get()).is_equal_to(&12);
+        p2.set(20);
+        assert_that(&sum.get()).is_equal_to(&30);
+    });
+}
+
+#[test]
+fn batch_returns_closure_value() {
+    let mut p = Property::new(1);
+    let old = batch(|| {
+        let old = *p.get();
+        p.set(2);
+        old
+    });
+    assert_that(&old).is_equal_to(&1);
+}
+
+#[test]
+fn nested_batches_fire_when_outermost_ends() {
+    let mut p = Property::new(0);
+    let count = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let count = count.clone();
+        l.listen_to(&p).with(move || count.set(count.get() + 1));
+    }
+
+    let outer = Transaction::begin();
+    batch(|| {
+        p.set(1);
+        batch(|| p.set(2));
+        assert_that(&notify::is_batching()).is_true();
+    });
+    assert_that(&count.get()).is_equal_to(&0);
+
+    outer.commit();
+    assert_that(&count.get()).is_equal_to(&1);
+    assert_that(&notify::is_batching()).is_false();
+}
+
+#[test]
+fn listeners_released_during_batch_are_not_fired() {
+    let mut p = Property::new(0);
+    let count = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let count = count.clone();
+        l.listen_to(&p).with(move || count.set(count.get() + 1));
+    }
+
+    batch(|| {
+        p.set(1);
+        l.release_all();
+    });
+    assert_that(&count.get()).is_equal_to(&0);
+}
+
+#[test]
+fn panic_inside_batch_drops_deferred_listeners() {
+    let mut prop = Property::new(0);
+    let doubled = prop.times(2);
+    let count = Rc::new(Cell::new(0));
+    let mut l = Listeners::new();
+    {
+        let count = count.clone();
+        l.listen_to(&prop).with(move || count.set(count.get() + 1));
+    }
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        batch(|| {
+            prop.set(1);
+            panic!("oops");
+        });
+    }));
+
+    assert_that(&result.is_err()).is_true();
+    assert_that(&count.get()).is_equal_to(&0);
+    assert_that(&notify::is_batching()).is_false();
+    assert_that(&doubled.get()).is_equal_to(&2);
+
+    // Notifications are no longer deferred
+    prop.set(2);
+    assert_that(&count.get()).is_equal_to(&1);
+}
+
+#[test]
+fn panic_inside_batch_does_not_fire_a_panicking_listener() {
+    use pebl::expr::IntoExpression;
+
+    let mut prop = Property::new(0);
+    let mut l = Listeners::new();
+    {
+        let prop = (&prop).into_expr();
+        l.listen_to(prop.clone()).with(move || assert!(prop.get() >= 0, "negative"));
+    }
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        batch(|| {
+            prop.set(-1);
+            panic!("first");
+        });
+    }));
+
+    let message = result.unwrap_err().downcast::<&str>().map(|message| *message).unwrap_or("");
+    assert_that(&message).is_equal_to("first");
+    assert_that(&notify::is_batching()).is_false();
+}
+
+#[test]
+fn handler_can_set_same_observable_through_ptr() {
+    use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+
+    let mut o = Observable::new(0);
+    let handler;
+    {
+        let o_ptr = ObservablePtr::new(&o);
+        handler = InvalidationHandler::new(move || {
+            let value = *o_ptr.deref().get();
+            if value > 10 {
+                o_ptr.clone().deref_mut().set(10); // Clamp
+            }
+        });
+    }
+    o.add_invalidation_handler(&handler);
+
+    o.set(5);
+    assert_that(o.get()).is_equal_to(&5);
+    o.set(50);
+    assert_that(o.get()).is_equal_to(&10);
+}
+
+#[test]
+fn handlers_setting_other_observables_are_queued_instead_of_recursing() {
+    use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+
+    notify::set_max_passes(10000);
+    let mut chain: Vec<Observable<i32>> = (0..5000).map(|_| Observable::new(0)).collect();
+    let mut handlers = Vec::new();
+    for i in 0..chain.len() - 1 {
+        let src_ptr = ObservablePtr::new(&chain[i]);
+        let dst_ptr = ObservablePtr::new(&chain[i + 1]);
+        let handler = InvalidationHandler::new(move || {
+            let value = *src_ptr.deref().get();
+            dst_ptr.clone().deref_mut().set(value);
+        });
+        chain[i].add_invalidation_handler(&handler);
+        handlers.push(handler);
+    }
+
+    chain[0].set(42);
+    assert_that(chain.last().unwrap().get()).is_equal_to(&42);
+}
+
+#[test]
+fn batch_with_many_invalidated_handlers_fires_each_once() {
+    use pebl::obsv::{InvalidationHandler, Observable};
+
+    let mut values: Vec<Observable<i32>> = (0..50000).map(|_| Observable::new(0)).collect();
+    let count = Rc::new(Cell::new(0));
+    let handlers: Vec<InvalidationHandler> = values.iter().map(|value| {
+        let count = count.clone();
+        let handler = InvalidationHandler::new(move || count.set(count.get() + 1));
+        value.add_invalidation_handler(&handler);
+        handler
+    }).collect();
+
+    batch(|| {
+        for value in &mut values {
+            value.set(1);
+            value.set(2);
+        }
+    });
+    assert_that(&count.get()).is_equal_to(&handlers.len());
+}
+
+#[test]
+fn ping_pong_handlers_stop_at_max_passes() {
+    use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+
+    notify::set_max_passes(20);
+    assert_that(&notify::max_passes()).is_equal_to(&20);
+
+    let mut ping = Observable::new(0);
+    let pong = Observable::new(0);
+    let count = Rc::new(Cell::new(0));
+    let ping_handler;
+    let pong_handler;
+    {
+        let count = count.clone();
+        let ping_ptr = ObservablePtr::new(&ping);
+        let pong_ptr = ObservablePtr::new(&pong);
+        ping_handler = InvalidationHandler::new(move || {
+            count.set(count.get() + 1);
+            let value = *ping_ptr.deref().get();
+            pong_ptr.clone().deref_mut().set(value + 1);
+        });
+    }
+    {
+        let count = count.clone();
+        let ping_ptr = ObservablePtr::new(&ping);
+        let pong_ptr = ObservablePtr::new(&pong);
+        pong_handler = InvalidationHandler::new(move || {
+            count.set(count.get() + 1);
+            let value = *pong_ptr.deref().get();
+            ping_ptr.clone().deref_mut().set(value + 1);
+        });
+    }
+    ping.add_invalidation_handler(&ping_handler);
+    pong.add_invalidation_handler(&pong_handler);
+
+    ping.set(1);
+    assert_that(&count.get()).is_equal_to(&20);
+    assert_that(&notify::hit_pass_limit()).is_true();
+
+    // Dispatching recovers afterwards
+    drop(pong_handler);
+    ping.set(100);
+    assert_that(&count.get()).is_equal_to(&21);
+    assert_that(pong.get()).is_equal_to(&101);
+    assert_that(&notify::hit_pass_limit()).is_false();
+}
+
+#[test]
+fn long_chain_past_max_passes_reports_hitting_the_limit() {
+    use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+
+    notify::set_max_passes(5);
+    let mut chain: Vec<Observable<i32>> = (0..10).map(|_| Observable::new(0)).collect();
+    let mut handlers = Vec::new();
+    for i in 0..chain.len() - 1 {
+        let src_ptr = ObservablePtr::new(&chain[i]);
+        let dst_ptr = ObservablePtr::new(&chain[i + 1]);
+        let handler = InvalidationHandler::new(move || {
+            let value = *src_ptr.deref().get();
+            dst_ptr.clone().deref_mut().set(value);
+        });
+        chain[i].add_invalidation_handler(&handler);
+        handlers.push(handler);
+    }
+
+    chain[0].set(42);
+    assert_that(&notify::hit_pass_limit()).is_true();
+    assert_that(chain.last().unwrap().get()).is_equal_to(&0);
+
+    notify::set_max_passes(100);
+    chain[0].set(43);
+    assert_that(&notify::hit_pass_limit()).is_false();
+    assert_that(chain.last().unwrap().get()).is_equal_to(&43);
+}
+
+#[test]
+fn caches_are_not_left_stale_when_dispatch_hits_the_pass_limit() {
+    notify::set_max_passes(10);
+    let p = Rc::new(RefCell::new(Property::new(0)));
+    let doubled = p.borrow().times(2);
+    assert_that(&doubled.get()).is_equal_to(&0);
+
+    let mut l = Listeners::new();
+    {
+        let (p, doubled) = (p.clone(), doubled.clone());
+        l.listen_to(doubled.clone()).with(move || {
+            doubled.get();
+            let value = *p.borrow().get();
+            p.borrow_mut().set(value + 1);
+        });
+    }
+
+    batch(|| p.borrow_mut().set(1));
+    assert_that(&notify::hit_pass_limit()).is_true();
+    assert_that(&doubled.get()).is_equal_to(&(*p.borrow().get() * 2));
+    notify::set_max_passes(notify::DEFAULT_MAX_PASSES);
+}
+
+#[test]
+fn caches_are_not_left_stale_when_a_handler_panics() {
+    let mut p = Property::new(1);
+    let doubled = p.times(2);
+    assert_that(&doubled.get()).is_equal_to(&2);
+
+    let mut l = Listeners::new();
+    l.listen_to(&p).with(|| panic!("oops"));
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| p.set(5)));
+    assert_that(&result.is_err()).is_true();
+    assert_that(&doubled.get()).is_equal_to(&10);
+}
+
+#[test]
+fn diamond_dependency_notifies_listener_once_with_consistent_values() {
+    let mut a = Property::new(1);
+    let doubled = Property::bound_to(expr::unary(&a, |a| a * 2));
+    let incremented = Property::bound_to(expr::unary(&a, |a| a + 1));
+    let sum = expr::binary(&doubled, &incremented, |d, i| d + i);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut l = Listeners::new();
+    {
+        let seen = seen.clone();
+        let sum = sum.clone();
+        l.listen_to(sum.clone()).with(move || seen.borrow_mut().push(sum.get()));
+    }
+
+    a.set(5);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![16]);
+
+    a.set(10);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![16, 31]);
+}
+
+#[test]
+fn uneven_paths_to_the_same_source_notify_listener_once() {
+    let mut a = Property::new(1);
+    let b = Property::bound_to(&a);
+    let c = Property::bound_to(&b);
+    let total = a.plus(&b).plus(&c);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut l = Listeners::new();
+    {
+        let seen = seen.clone();
+        let total = total.clone();
+        l.listen_to(total.clone()).with(move || seen.borrow_mut().push(total.get()));
+    }
+
+    a.set(2);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![6]);
+}
+
+#[test]
+fn fan_out_listeners_fire_in_dependency_order() {
+    let mut a = Property::new(1);
+    let plus_one = expr::unary(&a, |a| a + 1);
+    let plus_two = expr::unary(plus_one.clone(), |a| a + 1);
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let mut l = Listeners::new();
+    // Registered deepest first, to make sure registration order doesn't matter
+    {
+        let order = order.clone();
+        l.listen_to(plus_two.clone()).with(move || order.borrow_mut().push("plus_two"));
+    }
+    {
+        let order = order.clone();
+        l.listen_to(plus_one.clone()).with(move || order.borrow_mut().push("plus_one"));
+    }
+    {
+        let order = order.clone();
+        l.listen_to(&a).with(move || order.borrow_mut().push("a"));
+    }
+
+    a.set(2);
+    assert_that(&*order.borrow()).is_equal_to(&vec!["a", "plus_one", "plus_two"]);
+    assert_that(&plus_two.get()).is_equal_to(&4);
+}
+
+#[test]
+fn fan_in_listener_fires_once_per_batch() {
+    let mut a = Property::new(1);
+    let mut b = Property::new(10);
+    let mut c = Property::new(100);
+    let b_copy = Property::bound_to(&b);
+    let total = a.plus(&b_copy).plus(&c);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut l = Listeners::new();
+    {
+        let seen = seen.clone();
+        let total = total.clone();
+        l.listen_to(total.clone()).with(move || seen.borrow_mut().push(total.get()));
+    }
+
+    batch(|| {
+        a.set(2);
+        b.set(20);
+        c.set(200);
+    });
+    assert_that(&*seen.borrow()).is_equal_to(&vec![222]);
+
+    b.set(30);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![222, 232]);
+}
@@ -2,10 +2,10 @@
 extern crate spectral;
 extern crate pebl;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use spectral::prelude::*;
-use pebl::obsv::{InvalidationHandler, Observable, ObservablePtr};
+use pebl::obsv::{ChangeHandler, DisposalHandler, InvalidationHandler, Observable, ObservablePtr};
 
 #[test]
 fn default_values() {
@@ -109,6 +109,63 @@ fn observable_fires_invalidation_event_on_modify_inner() {
     assert_that(&count.get()).is_equal_to(&3);
 }
 
+#[test]
+fn change_handler_receives_old_and_new_values() {
+    let mut o = Observable::new(10);
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let changes = changes.clone();
+        handler = ChangeHandler::new(move |old: &i32, new: &i32| changes.borrow_mut().push((*old, *new)));
+    }
+    o.add_change_handler(&handler);
+
+    o.set(20);
+    o.set(20);
+    o.set(30);
+    assert_that(&*changes.borrow()).is_equal_to(&vec![(10, 20), (20, 30)]);
+
+    drop(handler);
+    o.set(40);
+    assert_that(&changes.borrow().len()).is_equal_to(&2);
+}
+
+#[test]
+fn change_handler_receives_snapshot_on_modify_inner() {
+    let mut o = Observable::new(vec![1, 2]);
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let changes = changes.clone();
+        handler = ChangeHandler::new(move |old: &Vec<i32>, new: &Vec<i32>| changes.borrow_mut().push((old.clone(), new.clone())));
+    }
+    o.add_change_handler(&handler);
+
+    o.modify_inner().push(3);
+    assert_that(&*changes.borrow()).is_equal_to(&vec![(vec![1, 2], vec![1, 2, 3])]);
+
+    // Modifying without actually changing the value doesn't report a change
+    o.modify_inner().len();
+    assert_that(&changes.borrow().len()).is_equal_to(&1);
+}
+
+#[test]
+fn change_handler_can_read_its_own_source() {
+    let o = Observable::new(1);
+    let mut o_ptr = ObservablePtr::new(&o);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let (o_ptr, seen) = (o_ptr.clone(), seen.clone());
+        handler = ChangeHandler::new(move |_: &i32, new: &i32| seen.borrow_mut().push((*new, *o_ptr.deref().get())));
+    }
+    o.add_change_handler(&handler);
+
+    o_ptr.deref_mut().set(2);
+    o_ptr.deref_mut().modify_inner().clone_from(&3);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![(2, 2), (3, 3)]);
+}
+
 #[test]
 fn dropping_invalidation_handler_unregisters_it_from_observable() {
     let mut o = Observable::new(10);
@@ -131,21 +188,27 @@ fn dropping_invalidation_handler_unregisters_it_from_observable() {
 }
 
 #[test]
-fn dropping_observable_triggers_invalidation_handlers_on_last_time() {
+fn dropping_observable_triggers_disposal_handlers_only() {
     let o = Observable::new(10);
-    let count = Rc::new(Cell::new(0));
+    let invalidated_count = Rc::new(Cell::new(0));
+    let disposed_count = Rc::new(Cell::new(0));
 
-    let handler;
+    let invalidation_handler;
+    let disposal_handler;
     {
-        let count = count.clone();
-        handler = InvalidationHandler::new(move || count.set(count.get() + 1));
+        let invalidated_count = invalidated_count.clone();
+        invalidation_handler = InvalidationHandler::new(move || invalidated_count.set(invalidated_count.get() + 1));
+        let disposed_count = disposed_count.clone();
+        disposal_handler = DisposalHandler::new(move || disposed_count.set(disposed_count.get() + 1));
     }
-    o.add_invalidation_handler(&handler);
+    o.add_invalidation_handler(&invalidation_handler);
+    o.add_disposal_handler(&disposal_handler);
 
-    assert_that(&count.get()).is_equal_to(&0);
+    assert_that(&disposed_count.get()).is_equal_to(&0);
 
     drop(o);
-    assert_that(&count.get()).is_equal_to(&1);
+    assert_that(&disposed_count.get()).is_equal_to(&1);
+    assert_that(&invalidated_count.get()).is_equal_to(&0);
 }
 
 #[test]
@@ -198,6 +261,37 @@ fn value_ptr_wraps_target_value() {
     assert_that(&p_ptr.try_deref_mut()).is_none();
 }
 
+#[test]
+fn value_ptr_follows_moved_observable() {
+    let o = Observable::new(10);
+    let mut o_ptr = ObservablePtr::new(&o);
+
+    let mut moved = vec![o];
+    o_ptr.deref_mut().set(20);
+    assert_that(moved[0].get()).is_equal_to(&20);
+
+    moved[0].set(30);
+    assert_that(o_ptr.deref().get()).is_equal_to(&30);
+
+    let o = moved.pop().unwrap();
+    assert_that(o_ptr.deref().get()).is_equal_to(&30);
+
+    drop(o);
+    assert_that(&o_ptr.try_deref()).is_none();
+}
+
+#[test]
+fn value_ref_stays_valid_if_observable_dropped_while_borrowed() {
+    let o = Observable::new(String::from("Hello"));
+    let o_ptr = ObservablePtr::new(&o);
+    let o_ref = o_ptr.deref();
+
+    drop(o);
+    assert_that(o_ref.get()).is_equal_to(String::from("Hello"));
+    drop(o_ref);
+    assert_that(&o_ptr.try_deref()).is_none();
+}
+
 #[test]
 #[should_panic(expected = "value already immutably borrowed")]
 #[allow(unused_variables)] // Variables needed to keep value references alive
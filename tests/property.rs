@@ -2,9 +2,11 @@
 extern crate spectral;
 extern crate pebl;
 
+use std::cell::RefCell;
 use std::rc::Rc;
 use spectral::prelude::*;
 use pebl::prelude::*;
+use pebl::validate::{self, ValidationError};
 
 #[test]
 fn default_properties() {
@@ -104,6 +106,42 @@ fn property_can_bind_to_other_property() {
     assert_that(p_dest.get()).is_equal_to(&9000);
 }
 
+#[test]
+fn properties_can_be_moved_while_bound() {
+    let mut p_src = Property::new(1);
+    let p_dest = Property::bound_to(&p_src);
+
+    let mut sources = vec![p_src];
+    let dests = vec![p_dest];
+    assert_that(dests[0].get()).is_equal_to(&1);
+
+    sources[0].set(2);
+    assert_that(dests[0].get()).is_equal_to(&2);
+
+    p_src = sources.pop().unwrap();
+    p_src.set(3);
+    assert_that(dests[0].get()).is_equal_to(&3);
+}
+
+#[test]
+fn property_holds_last_value_when_bound_source_is_dropped() {
+    let mut p_src = Property::new(1);
+    let mut p_other = Property::new(10);
+    let p_dest = Property::bound_to(p_src.plus(&p_other));
+    assert_that(p_dest.get()).is_equal_to(&11);
+
+    p_src.set(2);
+    p_other.set(20);
+    drop(p_src);
+
+    assert_that(&p_dest.is_bound()).is_false();
+    assert_that(p_dest.get()).is_equal_to(&22);
+
+    // No longer listening to the surviving source either
+    p_other.set(30);
+    assert_that(p_dest.get()).is_equal_to(&22);
+}
+
 #[test]
 fn property_can_bind_to_expression() {
     let mut p1 = Property::new(100);
@@ -136,3 +174,376 @@ fn bool_property_can_invert() {
     p.invert();
     assert_that(p.get()).is_true();
 }
+
+#[test]
+fn property_change_handler_receives_old_and_new_values() {
+    let mut p = Property::new(String::from("Hello"));
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let changes = changes.clone();
+        handler = ChangeHandler::new(move |old: &String, new: &String| changes.borrow_mut().push((old.clone(), new.clone())));
+    }
+    p.add_change_handler(&handler);
+
+    p.set(String::from("World"));
+    p.modify_inner().push_str("!");
+    assert_that(&*changes.borrow()).is_equal_to(&vec![
+        (String::from("Hello"), String::from("World")),
+        (String::from("World"), String::from("World!")),
+    ]);
+}
+
+#[test]
+fn property_change_handler_can_read_its_own_property() {
+    use pebl::expr::IntoExpression;
+
+    let mut src = Property::new(1);
+    let dst = Property::bound_to(&src);
+    let dst_expr = (&dst).into_expr();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let seen = seen.clone();
+        handler = ChangeHandler::new(move |_: &i32, new: &i32| seen.borrow_mut().push((*new, dst_expr.get())));
+    }
+    dst.add_change_handler(&handler);
+
+    src.set(2);
+    assert_that(dst.get()).is_equal_to(&2);
+    assert_that(&*seen.borrow()).is_equal_to(&vec![(2, 2)]);
+}
+
+#[test]
+fn bidirectional_binding_change_handler_can_read_its_own_property() {
+    use pebl::expr::IntoExpression;
+
+    let mut model = Property::new(String::from("a"));
+    let mut view = Property::new(String::new());
+    view.bind_bidirectional(&mut model);
+    let model_len = (&model).into_expr().len();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let handler;
+    {
+        let seen = seen.clone();
+        handler = ChangeHandler::new(move |_: &String, new: &String| seen.borrow_mut().push((new.clone(), model_len.get())));
+    }
+    model.add_change_handler(&handler);
+
+    view.set(String::from("abc"));
+    assert_that(&*seen.borrow()).is_equal_to(&vec![(String::from("abc"), 3)]);
+}
+
+#[test]
+fn property_validators_can_reject_values() {
+    let mut age = Property::new(30);
+    age.add_validator(validate::in_range(0, 150));
+
+    assert_that(&age.try_set(151)).is_err();
+    assert_that(age.get()).is_equal_to(&30);
+
+    age.set(-1);
+    assert_that(age.get()).is_equal_to(&30);
+
+    assert_that(&age.try_set(150)).is_ok();
+    assert_that(age.get()).is_equal_to(&150);
+}
+
+#[test]
+fn property_validators_can_change_values() {
+    let mut name = Property::new(String::from("guest"));
+    name.add_validator(|name: String| Ok(name.trim().to_string()));
+    name.add_validator(validate::not_empty());
+
+    name.set(String::from("  Jane  "));
+    assert_that(name.get()).is_equal_to(&String::from("Jane"));
+
+    assert_that(&name.try_set(String::from("   "))).is_err_containing(&ValidationError::new("must not be empty"));
+    assert_that(name.get()).is_equal_to(&String::from("Jane"));
+
+    let mut volume = Property::new(5);
+    volume.add_validator(validate::clamp(0, 10));
+    volume.set(-3);
+    assert_that(volume.get()).is_equal_to(&0);
+}
+
+#[test]
+fn property_validity_can_be_bound() {
+    let mut age = Property::new(30);
+    age.add_validator(validate::in_range(0, 150));
+    let is_valid = Property::bound_to(age.is_valid());
+    let error = Property::bound_to(age.validation_error());
+    let error_label = Property::bound_to(expr::unary(age.validation_error(), |error| error.clone().unwrap_or_default()));
+
+    assert_that(is_valid.get()).is_true();
+    assert_that(error.get()).is_none();
+
+    age.set(200);
+    assert_that(is_valid.get()).is_false();
+    assert_that(error_label.get()).is_equal_to(&String::from("must be between 0 and 150"));
+
+    age.set(40);
+    assert_that(is_valid.get()).is_true();
+    assert_that(error.get()).is_none();
+}
+
+#[test]
+fn property_validity_works_with_validators_added_later() {
+    let mut age = Property::new(30);
+    let is_valid = Property::bound_to(age.is_valid());
+    assert_that(is_valid.get()).is_true();
+
+    age.set(200);
+    assert_that(is_valid.get()).is_true();
+
+    age.add_validator(validate::in_range(0, 150));
+    age.set(300);
+    assert_that(is_valid.get()).is_false();
+    assert_that(age.get()).is_equal_to(&200);
+}
+
+#[test]
+fn property_bindings_bypass_validators() {
+    let mut src = Property::new(10);
+    let mut dest = Property::new(0);
+    dest.add_validator(validate::in_range(0, 100));
+    dest.bind(&src);
+    let is_valid = Property::bound_to(dest.is_valid());
+
+    src.set(500);
+    assert_that(dest.get()).is_equal_to(&500);
+    assert_that(is_valid.get()).is_true();
+}
+
+#[test]
+fn bidirectional_binding_updates_both_sides() {
+    let mut p1 = Property::new(1);
+    let mut p2 = Property::new(2);
+    p1.bind_bidirectional(&mut p2);
+
+    assert_that(p1.get()).is_equal_to(&2);
+    assert_that(p2.get()).is_equal_to(&2);
+
+    p1.set(10);
+    assert_that(p2.get()).is_equal_to(&10);
+
+    p2.set(20);
+    assert_that(p1.get()).is_equal_to(&20);
+
+    p1.modify_inner().clone_from(&30);
+    assert_that(p2.get()).is_equal_to(&30);
+}
+
+#[test]
+fn bidirectional_binding_last_write_wins() {
+    let mut p1 = Property::new(0);
+    let mut p2 = Property::new(0);
+    p1.bind_bidirectional(&mut p2);
+
+    p1.set(1);
+    p2.set(2);
+    assert_that(p1.get()).is_equal_to(&2);
+    assert_that(p2.get()).is_equal_to(&2);
+
+    p2.set(3);
+    p1.set(4);
+    assert_that(p2.get()).is_equal_to(&4);
+    assert_that(p1.get()).is_equal_to(&4);
+}
+
+#[test]
+fn bidirectional_binding_with_conversion_does_not_write_back() {
+    let mut count = Property::new(0);
+    let mut count_text = Property::new(String::new());
+    count_text.bind_bidirectional_with(&mut count, |text| text.parse().unwrap_or(0), |count| count.to_string());
+    assert_that(count_text.get()).is_equal_to(&String::from("0"));
+
+    let changes = Rc::new(RefCell::new(0));
+    let handler;
+    {
+        let changes = changes.clone();
+        handler = ChangeHandler::new(move |_: &String, _: &String| *changes.borrow_mut() += 1);
+    }
+    count_text.add_change_handler(&handler);
+
+    // "007" converts to 7, but should not come back around as "7"
+    count_text.set(String::from("007"));
+    assert_that(count.get()).is_equal_to(&7);
+    assert_that(count_text.get()).is_equal_to(&String::from("007"));
+    assert_that(&*changes.borrow()).is_equal_to(&1);
+
+    count.set(8);
+    assert_that(count_text.get()).is_equal_to(&String::from("8"));
+}
+
+#[test]
+fn bidirectional_binding_validates_values_it_pushes() {
+    let mut age = Property::new(30);
+    age.add_validator(validate::in_range(0, 150));
+    let age_valid = Property::bound_to(age.is_valid());
+    let mut age_text = Property::new(String::new());
+    age_text.bind_bidirectional_with(&mut age, |text| text.parse().unwrap_or(-1), |age| age.to_string());
+
+    age_text.set(String::from("200"));
+    assert_that(age.get()).is_equal_to(&30);
+    assert_that(age_text.get()).is_equal_to(&String::from("200"));
+    assert_that(age_valid.get()).is_false();
+
+    age_text.set(String::from("40"));
+    assert_that(age.get()).is_equal_to(&40);
+    assert_that(age_valid.get()).is_true();
+}
+
+#[test]
+fn bidirectional_binding_survives_a_panicking_conversion() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mut count = Property::new(0);
+    let mut count_text = Property::new(String::new());
+    count_text.bind_bidirectional_with(&mut count, |text| text.parse().expect("not a number"), |count| count.to_string());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| count_text.set(String::from("abc"))));
+    assert_that(&result.is_err()).is_true();
+
+    count_text.set(String::from("5"));
+    assert_that(count.get()).is_equal_to(&5);
+    count.set(6);
+    assert_that(count_text.get()).is_equal_to(&String::from("6"));
+}
+
+#[test]
+fn bidirectional_binding_can_be_unbound_from_either_side() {
+    let mut p1 = Property::new(1);
+    let mut p2 = Property::new(2);
+    p1.bind_bidirectional(&mut p2);
+    assert_that(&p1.is_bound()).is_true();
+    assert_that(&p2.is_bound()).is_true();
+
+    p2.unbind();
+    assert_that(&p1.is_bound()).is_false();
+    assert_that(&p2.is_bound()).is_false();
+
+    p2.set(3);
+    assert_that(p1.get()).is_equal_to(&2);
+    p1.set(4);
+    assert_that(p2.get()).is_equal_to(&3);
+
+    let mut p3 = Property::new(5);
+    p1.bind_bidirectional(&mut p3);
+    drop(p3);
+    assert_that(&p1.is_bound()).is_false();
+    assert_that(p1.get()).is_equal_to(&5);
+}
+
+#[test]
+fn bound_property_notifies_its_listeners_without_being_read() {
+    let mut l = Listeners::new();
+    let mut p_src = Property::new(1);
+    let p_dest = Property::bound_to(&p_src);
+    let listen_count = Rc::new(RefCell::new(0));
+    {
+        let listen_count = listen_count.clone();
+        l.listen_to(&p_dest).with(move || *listen_count.borrow_mut() += 1);
+    }
+
+    p_src.set(2);
+    assert_that(&*listen_count.borrow()).is_equal_to(&1);
+    p_src.set(3);
+    assert_that(&*listen_count.borrow()).is_equal_to(&2);
+
+    // Reading the value pulls it in, but doesn't notify a second time
+    assert_that(p_dest.get()).is_equal_to(&3);
+    assert_that(&*listen_count.borrow()).is_equal_to(&2);
+}
+
+#[test]
+fn chains_of_bound_properties_propagate_changes() {
+    let mut l = Listeners::new();
+    let mut p_a = Property::new(1);
+    let p_b = Property::bound_to(&p_a);
+    let p_c = Property::bound_to(&p_b);
+    let p_d = Property::bound_to(expr::unary(&p_c, |c| c * 10));
+    let last_seen = Rc::new(RefCell::new(0));
+    {
+        let last_seen = last_seen.clone();
+        let expr_d = expr::unary(&p_d, |d| *d);
+        l.listen_to(&p_d).with(move || *last_seen.borrow_mut() = expr_d.get());
+    }
+
+    p_a.set(2);
+    assert_that(&*last_seen.borrow()).is_equal_to(&20);
+    assert_that(p_c.get()).is_equal_to(&2);
+    assert_that(p_b.get()).is_equal_to(&2);
+
+    p_a.set(3);
+    assert_that(p_d.get()).is_equal_to(&30);
+    assert_that(&*last_seen.borrow()).is_equal_to(&30);
+}
+
+#[test]
+fn read_only_property_can_be_read_and_listened_to() {
+    let mut l = Listeners::new();
+    let mut p = Property::new(1);
+    let listen_count = Rc::new(RefCell::new(0));
+    let view = p.read_only();
+    assert_that(&view.get()).is_equal_to(&1);
+    assert_that(&view.is_bound()).is_false();
+
+    let listen_count_cloned = listen_count.clone();
+    l.listen_to(&view).with(move || *listen_count_cloned.borrow_mut() += 1);
+    let doubled = Property::bound_to(p.read_only().plus(&p));
+    let is_big = Property::bound_to((&view).gt_val(5));
+
+    p.set(10);
+    assert_that(&*listen_count.borrow()).is_equal_to(&1);
+    assert_that(doubled.get()).is_equal_to(&20);
+    assert_that(is_big.get()).is_true();
+    assert_that(&view.get()).is_equal_to(&10);
+}
+
+#[test]
+fn read_only_property_can_be_kept_while_the_property_changes() {
+    struct Counter {
+        count: Property<u32>,
+    }
+
+    struct Display {
+        count: ReadOnlyProperty<u32>,
+    }
+
+    let mut counter = Counter { count: Property::new(0) };
+    let display = Display { count: counter.count.read_only() };
+    let text = Property::bound_to(expr::unary(&display.count, |c| format!("{} clicks", c)));
+
+    for i in 1..4 {
+        counter.count.set(i);
+        assert_that(&display.count.get()).is_equal_to(&i);
+    }
+    assert_that(text.get()).is_equal_to(&"3 clicks".to_string());
+
+    drop(counter);
+    assert_that(&display.count.try_get().is_none()).is_true();
+    assert_that(&display.count.is_bound()).is_false();
+}
+
+#[test]
+fn read_only_property_values_are_unaffected_by_later_sets() {
+    let mut owner = Property::new(String::from("old"));
+    let view = owner.read_only();
+
+    let value = view.get();
+    owner.set(String::from("new"));
+    assert_that(&value).is_equal_to(String::from("old"));
+    assert_that(&view.get()).is_equal_to(String::from("new"));
+}
+
+#[test]
+fn read_only_property_reflects_bindings() {
+    let mut p_src = Property::new(1);
+    let p_dest = Property::bound_to(&p_src);
+    let p_view_dest = Property::bound_to(p_dest.read_only());
+
+    p_src.set(2);
+    assert_that(&p_dest.read_only().is_bound()).is_true();
+    assert_that(p_view_dest.get()).is_equal_to(&2);
+}
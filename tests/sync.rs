@@ -0,0 +1,191 @@
+#[macro_use]
+extern crate spectral;
+extern crate pebl;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use spectral::prelude::*;
+use pebl::equality::PtrEq;
+use pebl::sync::{self, SyncDisposalHandler, SyncInvalidationHandler, SyncObservable, SyncProperty, SyncWeakList};
+
+#[test]
+fn sync_types_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncObservable<i32>>();
+    assert_send_sync::<SyncProperty<String>>();
+    assert_send_sync::<SyncWeakList<i32>>();
+    assert_send_sync::<SyncInvalidationHandler>();
+    assert_send_sync::<SyncDisposalHandler>();
+    assert_send_sync::<Arc<sync::SyncExpression<bool>>>();
+}
+
+#[test]
+fn sync_observable_can_be_observed_from_other_threads() {
+    let o = Arc::new(SyncObservable::new(10));
+    let count = Arc::new(AtomicUsize::new(0));
+    let handler;
+    {
+        let count = count.clone();
+        handler = SyncInvalidationHandler::new(move || { count.fetch_add(1, Ordering::SeqCst); });
+    }
+    o.add_invalidation_handler(&handler);
+
+    let threads: Vec<_> = (1..5).map(|i| {
+        let o = o.clone();
+        thread::spawn(move || o.set(i * 100))
+    }).collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&4);
+
+    // Setting same value doesn't increment count
+    let current = *o.get();
+    o.set(current);
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&4);
+}
+
+#[test]
+fn sync_observable_fires_invalidation_event_on_modify_inner() {
+    let o = SyncObservable::new(String::from("Hello"));
+    let count = Arc::new(AtomicUsize::new(0));
+    let handler;
+    {
+        let count = count.clone();
+        handler = SyncInvalidationHandler::new(move || { count.fetch_add(1, Ordering::SeqCst); });
+    }
+    o.add_invalidation_handler(&handler);
+
+    o.modify_inner().push_str(", World");
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&1);
+    assert_that(&*o.get()).is_equal_to(String::from("Hello, World"));
+}
+
+#[test]
+fn sync_observable_fires_disposal_event_instead_of_invalidation_when_dropped() {
+    let o = SyncObservable::new(10);
+    let invalidated = Arc::new(AtomicBool::new(false));
+    let disposed = Arc::new(AtomicBool::new(false));
+    let invalidation_handler;
+    let disposal_handler;
+    {
+        let invalidated = invalidated.clone();
+        invalidation_handler = SyncInvalidationHandler::new(move || invalidated.store(true, Ordering::SeqCst));
+        let disposed = disposed.clone();
+        disposal_handler = SyncDisposalHandler::new(move || disposed.store(true, Ordering::SeqCst));
+    }
+    o.add_invalidation_handler(&invalidation_handler);
+    o.add_disposal_handler(&disposal_handler);
+
+    thread::spawn(move || drop(o)).join().unwrap();
+    assert_that(&invalidated.load(Ordering::SeqCst)).is_false();
+    assert_that(&disposed.load(Ordering::SeqCst)).is_true();
+}
+
+#[test]
+fn sync_expressions_forward_disposal_of_their_sources() {
+    let p1 = SyncProperty::new(1);
+    let p2 = SyncProperty::new(2);
+    let sum = sync::expr::binary(&p1, sync::expr::unary(&p2, |val| val * 10), |lhs, rhs| lhs + rhs);
+    let count = Arc::new(AtomicUsize::new(0));
+    let handler;
+    {
+        let count = count.clone();
+        handler = SyncDisposalHandler::new(move || { count.fetch_add(1, Ordering::SeqCst); });
+    }
+    sum.add_disposal_handler(&handler);
+
+    drop(p2);
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&1);
+    drop(p1);
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&2);
+}
+
+#[test]
+fn sync_property_keeps_last_value_when_binding_source_is_dropped() {
+    let p_src = SyncProperty::new(42);
+    let p_dest = SyncProperty::bound_to(&p_src);
+    p_src.set(43);
+
+    drop(p_src);
+    assert_that(&*p_dest.get()).is_equal_to(&43);
+}
+
+#[test]
+fn sync_property_uses_equality_policy() {
+    struct Document { title: String } // Not PartialEq
+
+    let doc = Arc::new(Document { title: String::from("Untitled") });
+    let p = SyncProperty::with_policy(doc.clone(), PtrEq);
+    let title = sync::expr::unary(&p, |doc| doc.title.clone());
+    let count = Arc::new(AtomicUsize::new(0));
+    let handler;
+    {
+        let count = count.clone();
+        handler = SyncInvalidationHandler::new(move || { count.fetch_add(1, Ordering::SeqCst); });
+    }
+    p.add_invalidation_handler(&handler);
+
+    p.set(doc.clone());
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&0);
+
+    p.set(Arc::new(Document { title: String::from("Untitled") }));
+    assert_that(&count.load(Ordering::SeqCst)).is_equal_to(&1);
+
+    let p = Arc::new(p);
+    {
+        let p = p.clone();
+        thread::spawn(move || p.set(Arc::new(Document { title: String::from("Notes") }))).join().unwrap();
+    }
+    assert_that(&title.get()).is_equal_to(String::from("Notes"));
+}
+
+#[test]
+fn sync_property_can_bind_across_threads() {
+    let p_src = Arc::new(SyncProperty::new(42));
+    let p_dest = Arc::new(SyncProperty::default());
+
+    p_dest.bind(sync::expr::unary(&*p_src, |val| val + 1));
+    assert_that(&*p_dest.get()).is_equal_to(&43);
+
+    {
+        let p_src = p_src.clone();
+        thread::spawn(move || p_src.set(9000)).join().unwrap();
+    }
+    {
+        let p_dest = p_dest.clone();
+        let value = thread::spawn(move || *p_dest.get()).join().unwrap();
+        assert_that(&value).is_equal_to(&9001);
+    }
+
+    p_dest.unbind();
+    assert_that(&p_dest.is_bound()).is_false();
+    p_src.set(123);
+    assert_that(&*p_dest.get()).is_equal_to(&9001);
+}
+
+#[test]
+fn sync_binary_expression_works() {
+    let p1 = SyncProperty::new(String::from("Hello"));
+    let p2 = SyncProperty::new(String::from("World"));
+    let e = sync::expr::binary(&p1, &p2, |p1, p2| format!("{}, {}", p1, p2));
+    assert_that(&e.get()).is_equal_to(String::from("Hello, World"));
+
+    p1.set(String::from("Goodbye"));
+    assert_that(&e.get()).is_equal_to(String::from("Goodbye, World"));
+}
+
+#[test]
+fn sync_weak_list_is_cleaned_up() {
+    let list = SyncWeakList::<i32>::new();
+    let int1 = Arc::new(10);
+    list.push(&int1);
+    {
+        let int2 = Arc::new(20);
+        list.push(&int2);
+        assert_that(&list.len()).is_equal_to(&2);
+    }
+    assert_that(&list.upgrade().len()).is_equal_to(&1);
+}